@@ -35,7 +35,50 @@ pub mod program {
 ///
 /// The second argument is the maximum number of accounts that the program is expecting. A program
 /// can receive more than the specified maximum, but any account exceeding the maximum will be
-/// ignored.
+/// ignored. Any constant expression can be used, e.g., the `MAX_ACCOUNTS` constant generated by
+/// the `Accounts` derive macro.
+///
+/// Optionally, `pre` and `post` hook functions can be specified (in this order) to add
+/// cross-cutting concerns – e.g., program id checks, compute units logging or a global
+/// pause switch – without changing the instruction processor:
+///
+/// ```ignore
+/// // Called after the input is deserialized; when it returns an error, the instruction
+/// // is not processed.
+/// fn pre(
+///     program_id: &Pubkey,
+///     accounts: &[AccountInfo],
+///     instruction_data: &[u8],
+/// ) -> ProgramResult;
+///
+/// // Called with the result of the instruction (or the `pre` hook) before returning;
+/// // its result is reported to the runtime.
+/// fn post(
+///     program_id: &Pubkey,
+///     accounts: &[AccountInfo],
+///     instruction_data: &[u8],
+///     result: ProgramResult,
+/// ) -> ProgramResult;
+///
+/// entrypoint!(process_instruction, 10, pre = pre, post = post);
+/// ```
+///
+/// A minimum number of compute units can also be specified with `min_cu` (before any
+/// hook). The remaining compute units are checked right after the input is
+/// deserialized and, when below the minimum, the instruction is not processed and
+/// `ProgramError::Custom(INSUFFICIENT_COMPUTE_UNITS)` is returned – from the
+/// [`compute_budget`](nitrate_program::compute_budget) module – so instructions do not
+/// run out of compute units after partially updating their state:
+///
+/// ```ignore
+/// entrypoint!(process_instruction, 10, min_cu = 50_000);
+/// ```
+///
+/// When the `debug-lamports` feature is enabled, the entrypoint also verifies that the
+/// total lamports of the writable accounts is conserved by the instruction, logging the
+/// change of every account otherwise; the check is skipped when the instruction has more
+/// than `$maximum` accounts (see
+/// [`LamportSnapshot`](nitrate_program::lamports::LamportSnapshot)).
 ///
 /// # Examples
 ///
@@ -70,7 +113,7 @@ pub mod program {
 /// ```
 #[macro_export]
 macro_rules! entrypoint {
-    ( $process_instruction:ident, $maximum:literal ) => {
+    ( $process_instruction:ident, $maximum:expr $(, min_cu = $min_cu:expr)? $(, pre = $pre:expr)? $(, post = $post:expr)? ) => {
         #[no_mangle]
         pub unsafe extern "C" fn entrypoint(input: *mut u8) -> u64 {
             // create an array of uninitialized account infos; it is safe to `assume_init` since
@@ -80,15 +123,32 @@ macro_rules! entrypoint {
                 std::mem::MaybeUninit::uninit().assume_init();
 
             let (program_id, count, instruction_data) =
-                $crate::program::deserialize::<$maximum>(input, accounts.as_mut_ptr());
+                $crate::program::deserialize::<{ $maximum }>(input, accounts.as_mut_ptr());
+
+            // we know that `count` account infos are initialized so we cast the pointer
+            // to a slice of `[AccountInfo]`
+            let accounts: &[$crate::program::AccountInfo] =
+                std::slice::from_raw_parts(accounts.as_ptr() as _, count);
+
+            let result: solana_program::entrypoint::ProgramResult = Ok(());
+            // compute units guard
+            $( let result = $crate::program::compute_budget::require_remaining($min_cu); )?
+            // pre hook
+            $( let result = result.and_then(|()| $pre(&program_id, accounts, &instruction_data)); )?
+            // lamports conservation (only checked with the `debug-lamports` feature)
+            let snapshot = $crate::program::lamports::LamportSnapshot::<{ $maximum }>::new(
+                accounts,
+                // the total number of accounts is the first field of the input
+                *(input as *const u64) as usize,
+            );
+            // call the program's entrypoint passing the account infos
+            let result =
+                result.and_then(|()| $process_instruction(&program_id, accounts, &instruction_data));
+            let result = result.and_then(|()| snapshot.verify(accounts));
+            // post hook
+            $( let result = $post(&program_id, accounts, &instruction_data, result); )?
 
-            // call the program's entrypoint passing `count` account infos; we know that
-            // they are initialized so we cast the pointer to a slice of `[AccountInfo]`
-            match $process_instruction(
-                &program_id,
-                std::slice::from_raw_parts(accounts.as_ptr() as _, count),
-                &instruction_data,
-            ) {
+            match result {
                 Ok(()) => solana_program::entrypoint::SUCCESS,
                 Err(error) => error.into(),
             }
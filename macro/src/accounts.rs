@@ -1,6 +1,11 @@
 use proc_macro2::TokenStream;
 use quote::quote;
-use syn::{self, DeriveInput, Error, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Result};
+use syn::{
+    self,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    DeriveInput, Error, Lit, Meta, MetaList, MetaNameValue, NestedMeta, Result, Token,
+};
 
 // Constants for the account attribute.
 const ACCOUNT_TOKEN: &str = "account";
@@ -11,6 +16,57 @@ const NAME_TOKEN: &str = "name";
 // Constants for the account attribute optional property.
 const OPTIONAL_TOKEN: &str = "optional";
 
+// Constants for the account attribute signer property.
+const SIGNER_TOKEN: &str = "signer";
+
+// Constants for the account attribute writable property.
+const WRITABLE_TOKEN: &str = "writable";
+
+// Constants for the account attribute program constraint.
+const PROGRAM_TOKEN: &str = "program";
+
+// Constants for the account attribute address property (program constraint).
+const ADDRESS_TOKEN: &str = "address";
+
+// Constants for the account attribute token mint constraint.
+const TOKEN_MINT_TOKEN: &str = "token::mint";
+
+// Constants for the account attribute token authority constraint.
+const TOKEN_AUTHORITY_TOKEN: &str = "token::authority";
+
+// Constants for the account attribute init constraint.
+const INIT_TOKEN: &str = "init";
+
+// Constants for the account attribute payer property (init constraint).
+const PAYER_TOKEN: &str = "payer";
+
+// Constants for the account attribute space property (init constraint).
+const SPACE_TOKEN: &str = "space";
+
+// Constants for the account attribute seeds property (init constraint).
+const SEEDS_TOKEN: &str = "seeds";
+
+// Constants for the account attribute close constraint.
+const CLOSE_TOKEN: &str = "close";
+
+// Constants for the account attribute realloc constraint.
+const REALLOC_TOKEN: &str = "realloc";
+const REALLOC_PAYER_TOKEN: &str = "realloc::payer";
+const REALLOC_ZERO_TOKEN: &str = "realloc::zero";
+
+// Constants for the account attribute owner constraint.
+const OWNER_TOKEN: &str = "owner";
+
+// Names of the methods generated for each instruction; accounts with one of these
+// names do not get an accessor method, since it would clash with the method.
+const RESERVED_NAMES: &[&str] = &["context", "account_by_name", "close_accounts"];
+
+// Constants for the group attribute.
+const GROUP_TOKEN: &str = "group";
+
+// Constants for the group attribute type property.
+const TYPE_TOKEN: &str = "ty";
+
 /// Generates the account structs for each variant of the enum.
 pub fn generate_accounts(ast: DeriveInput) -> Result<TokenStream> {
     // parses each variant of the enum:
@@ -35,62 +91,9 @@ pub fn generate_accounts(ast: DeriveInput) -> Result<TokenStream> {
                     let ident = path.ident.to_string();
 
                     if ident == ACCOUNT_TOKEN {
-                        let meta_tokens = a
-                            .parse_meta()
-                            .map_err(|_error| Error::new_spanned(a, "#[account] is required"))?;
-
-                        let nested_meta = if let Meta::List(MetaList { nested, .. }) = &meta_tokens
-                        {
-                            nested
-                        } else {
-                            return Err(Error::new_spanned(a, "#[account] is required"));
-                        };
-
-                        let mut property: (Option<String>, Option<String>) = (None, None);
-
-                        for element in nested_meta {
-                            match element {
-                                NestedMeta::Meta(Meta::NameValue(MetaNameValue {
-                                    path,
-                                    lit,
-                                    ..
-                                })) => {
-                                    let ident = path.get_ident();
-                                    if let Some(ident) = ident {
-                                        if *ident == NAME_TOKEN {
-                                            let token = match lit {
-                                                Lit::Str(lit) => {
-                                                    lit.token().to_string().replace('\"', "")
-                                                }
-                                                _ => {
-                                                    return Err(Error::new_spanned(
-                                                        ident,
-                                                        "invalid value for \'name\' property",
-                                                    ));
-                                                }
-                                            };
-                                            property.0 = Some(token);
-                                        }
-                                    }
-                                }
-                                NestedMeta::Meta(Meta::Path(path)) => {
-                                    let name = path.get_ident().map(|x| x.to_string());
-                                    if let Some(name) = name {
-                                        if name == OPTIONAL_TOKEN {
-                                            property.1 = Some(name);
-                                        }
-                                    }
-                                }
-                                _ => {}
-                            }
-                        }
-                        instruction.accounts.push(Account {
-                            name: property.0.ok_or(Error::new_spanned(
-                                a,
-                                "account \'name\' property is required",
-                            ))?,
-                            optional: property.1.is_some(),
-                        });
+                        instruction.accounts.push(parse_account(a)?);
+                    } else if ident == GROUP_TOKEN {
+                        instruction.accounts.push(parse_group(a)?);
                     }
                 }
             }
@@ -106,14 +109,208 @@ pub fn generate_accounts(ast: DeriveInput) -> Result<TokenStream> {
     Ok(render_accounts(&instructions))
 }
 
+/// Parses an `#[account(...)]` attribute.
+///
+/// The attribute is a list of properties, which can be literals (e.g., the index of
+/// the account), flags (e.g., `signer`) or name-value pairs where the value is an
+/// expression (e.g., `name = "..."` or `token::mint = mint`).
+fn parse_account(attribute: &syn::Attribute) -> Result<Account> {
+    let properties = attribute
+        .parse_args_with(Punctuated::<Property, Token![,]>::parse_terminated)
+        .map_err(|_error| Error::new_spanned(attribute, "#[account] is required"))?;
+
+    let mut account = Account::default();
+    let mut name = None;
+
+    for property in properties {
+        let (path, value) = match property.path {
+            Some(path) => (path, property.value),
+            None => continue,
+        };
+
+        let key = path
+            .segments
+            .iter()
+            .map(|segment| segment.ident.to_string())
+            .collect::<Vec<_>>()
+            .join("::");
+
+        match (key.as_str(), value) {
+            (NAME_TOKEN, Some(value)) => {
+                name = Some(string_value(&key, &value)?);
+            }
+            (ADDRESS_TOKEN, Some(value)) => account.address = Some(value),
+            (TOKEN_MINT_TOKEN, Some(value)) => account.token_mint = Some(value),
+            (TOKEN_AUTHORITY_TOKEN, Some(value)) => account.token_authority = Some(value),
+            (PAYER_TOKEN, Some(value)) => account.payer = Some(value),
+            (SPACE_TOKEN, Some(value)) => account.space = Some(value),
+            (SEEDS_TOKEN, Some(value)) => match value {
+                syn::Expr::Array(array) => account.seeds = Some(array.elems.into_iter().collect()),
+                _ => {
+                    return Err(Error::new_spanned(
+                        value,
+                        "invalid value for \'seeds\' property",
+                    ));
+                }
+            },
+            (INIT_TOKEN, None) => account.init = true,
+            (CLOSE_TOKEN, Some(value)) => account.close = Some(value),
+            (OWNER_TOKEN, Some(value)) => account.owner = Some(value),
+            (OWNER_TOKEN, None) => account.owner = Some(syn::parse_quote!(crate::ID)),
+            (REALLOC_TOKEN, Some(value)) => account.realloc = Some(value),
+            (REALLOC_PAYER_TOKEN, Some(value)) => account.realloc_payer = Some(value),
+            (REALLOC_ZERO_TOKEN, Some(value)) => account.realloc_zero = Some(value),
+            (OPTIONAL_TOKEN, None) => account.optional = true,
+            (SIGNER_TOKEN, None) => account.signer = true,
+            (WRITABLE_TOKEN, None) => account.writable = true,
+            (PROGRAM_TOKEN, None) => account.program = true,
+            _ => {}
+        }
+    }
+
+    if account.address.is_some() && !account.program {
+        return Err(Error::new_spanned(
+            attribute,
+            "\'address\' property requires the \'program\' constraint",
+        ));
+    }
+
+    if account.init {
+        if account.optional {
+            return Err(Error::new_spanned(
+                attribute,
+                "\'init\' constraint cannot be used with optional accounts",
+            ));
+        }
+
+        if account.payer.is_none() || account.space.is_none() {
+            return Err(Error::new_spanned(
+                attribute,
+                "\'init\' constraint requires the \'payer\' and \'space\' properties",
+            ));
+        }
+    } else if account.payer.is_some() || account.space.is_some() || account.seeds.is_some() {
+        return Err(Error::new_spanned(
+            attribute,
+            "\'payer\', \'space\' and \'seeds\' properties require the \'init\' constraint",
+        ));
+    }
+
+    if account.realloc.is_some() {
+        if !account.writable {
+            return Err(Error::new_spanned(
+                attribute,
+                "\'realloc\' constraint requires the account to be writable",
+            ));
+        }
+
+        if account.realloc_payer.is_none() {
+            return Err(Error::new_spanned(
+                attribute,
+                "\'realloc\' constraint requires the \'realloc::payer\' property",
+            ));
+        }
+    } else if account.realloc_payer.is_some() || account.realloc_zero.is_some() {
+        return Err(Error::new_spanned(
+            attribute,
+            "\'realloc::payer\' and \'realloc::zero\' properties require the \'realloc\' constraint",
+        ));
+    }
+
+    if account.close.is_some() && !account.writable {
+        return Err(Error::new_spanned(
+            attribute,
+            "\'close\' constraint requires the account to be writable",
+        ));
+    }
+
+    account.name = name.ok_or(Error::new_spanned(
+        attribute,
+        "account \'name\' property is required",
+    ))?;
+
+    Ok(account)
+}
+
+/// Returns the value of a string literal property.
+fn string_value(key: &str, value: &syn::Expr) -> Result<String> {
+    match value {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: Lit::Str(lit), ..
+        }) => Ok(lit.value()),
+        _ => Err(Error::new_spanned(
+            value,
+            format!("invalid value for \'{key}\' property"),
+        )),
+    }
+}
+
+/// Parses a `#[group(name = "...", ty = "...")]` attribute.
+///
+/// A group represents a set of accounts defined by a struct deriving `AccountGroup`,
+/// which occupies as many positions as the number of accounts in the group.
+fn parse_group(attribute: &syn::Attribute) -> Result<Account> {
+    let meta_tokens = attribute
+        .parse_meta()
+        .map_err(|_error| Error::new_spanned(attribute, "#[group] is required"))?;
+
+    let nested_meta = if let Meta::List(MetaList { nested, .. }) = &meta_tokens {
+        nested
+    } else {
+        return Err(Error::new_spanned(attribute, "#[group] is required"));
+    };
+
+    let mut property: (Option<String>, Option<syn::Path>) = (None, None);
+
+    for element in nested_meta {
+        if let NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) = element {
+            if let Some(ident) = path.get_ident() {
+                let value = match lit {
+                    Lit::Str(lit) => lit,
+                    _ => {
+                        return Err(Error::new_spanned(
+                            ident,
+                            format!("invalid value for \'{ident}\' property"),
+                        ));
+                    }
+                };
+
+                if *ident == NAME_TOKEN {
+                    property.0 = Some(value.value());
+                } else if *ident == TYPE_TOKEN {
+                    property.1 = Some(value.parse()?);
+                }
+            }
+        }
+    }
+
+    Ok(Account {
+        name: property.0.ok_or(Error::new_spanned(
+            attribute,
+            "group \'name\' property is required",
+        ))?,
+        group: Some(property.1.ok_or(Error::new_spanned(
+            attribute,
+            "group \'ty\' property is required",
+        ))?),
+        ..Default::default()
+    })
+}
+
 /// Renders a struct for each enum variant (instruction).
 fn render_accounts(instructions: &[Instruction]) -> TokenStream {
     let instruction_structs = instructions.iter().map(|instruction| {
         let name = syn::parse_str::<syn::Ident>(&instruction.name).unwrap();
+        // position of each account (group accounts are expanded in place)
+        let (indices, expected) = account_indices(&instruction.accounts);
         // fields
         let struct_fields = instruction.accounts.iter().map(|account| {
             let account_name = syn::parse_str::<syn::Ident>(&account.name).unwrap();
-            if account.optional {
+            if let Some(group) = &account.group {
+                quote! {
+                    pub #account_name: #group<'a>
+                }
+            } else if account.optional {
                 quote! {
                     pub #account_name: Option<&'a nitrate::program::AccountInfo>
                 }
@@ -124,47 +321,554 @@ fn render_accounts(instructions: &[Instruction]) -> TokenStream {
             }
         });
         // initialization
-        let account_fields = instruction.accounts.iter().enumerate().map(|(index, account)| {
+        let account_bindings = instruction.accounts.iter().zip(indices.iter()).map(|(account, index)| {
             let account_name = syn::parse_str::<syn::Ident>(&account.name).unwrap();
 
+            if let Some(group) = &account.group {
+                quote! {
+                    let #account_name = #group::from_accounts(&accounts[#index..]);
+                }
+            } else if account.optional {
+                quote! {
+                    let #account_name = if accounts[#index].key() == &crate::ID { None } else { Some(&accounts[#index]) };
+                }
+            } else {
+                quote! {
+                    let #account_name = &accounts[#index];
+                }
+            }
+        });
+        let account_fields = instruction.accounts.iter().map(|account| {
+            syn::parse_str::<syn::Ident>(&account.name).unwrap()
+        });
+        // constraints
+        let constraints = instruction.accounts.iter().map(render_constraints);
+        let owners = render_owners(&instruction.accounts, false);
+        let inits = instruction.accounts.iter().map(render_init);
+        let init_owners = render_owners(&instruction.accounts, true);
+        let reallocs = instruction.accounts.iter().map(render_realloc);
+        // accessors
+        let accessors = instruction.accounts.iter().filter(|account| !RESERVED_NAMES.contains(&account.name.as_str())).map(|account| {
+            let account_name = syn::parse_str::<syn::Ident>(&account.name).unwrap();
+
+            if let Some(group) = &account.group {
+                quote! {
+                    #[inline(always)]
+                    pub fn #account_name(&self) -> &#group<'a> {
+                        &self.#account_name
+                    }
+                }
+            } else if account.optional {
+                quote! {
+                    #[inline(always)]
+                    pub fn #account_name(&self) -> Option<&'a nitrate::program::AccountInfo> {
+                        self.#account_name
+                    }
+                }
+            } else {
+                quote! {
+                    #[inline(always)]
+                    pub fn #account_name(&self) -> &'a nitrate::program::AccountInfo {
+                        self.#account_name
+                    }
+                }
+            }
+        });
+        // metadata
+        let metadata = instruction.accounts.iter().zip(indices.iter()).map(|(account, index)| {
+            let literal = &account.name;
+            let (signer, writable, optional) = (account.signer, account.writable, account.optional);
+            let constraints = constraint_names(account);
+
+            quote! {
+                AccountMetadata {
+                    name: #literal,
+                    index: #index,
+                    signer: #signer,
+                    writable: #writable,
+                    optional: #optional,
+                    constraints: &[#(#constraints,)*],
+                }
+            }
+        });
+        // named lookup (group accounts are accessed through the group)
+        let named_accounts = instruction.accounts.iter().filter(|account| account.group.is_none()).map(|account| {
+            let account_name = syn::parse_str::<syn::Ident>(&account.name).unwrap();
+            let literal = &account.name;
+
             if account.optional {
                 quote! {
-                    #account_name: if accounts[#index].key() == &crate::ID { None } else { Some(&accounts[#index]) }
+                    #literal => self.#account_name.ok_or(solana_program::program_error::ProgramError::NotEnoughAccountKeys)
                 }
             } else {
                 quote! {
-                    #account_name: &accounts[#index]
+                    #literal => Ok(self.#account_name)
                 }
             }
         });
-        // expected accounts
-        let expected = instruction.accounts.len();
+
+        // close (only rendered when accounts declare the constraint)
+        let close = render_close(&instruction.accounts);
 
         quote! {
             pub struct #name<'a> {
                 #(#struct_fields,)*
             }
             impl<'a> #name<'a> {
+                pub const METADATA: &'static [AccountMetadata] = &[#(#metadata,)*];
+
                 #[inline(always)]
                 pub fn context(accounts: &'a [nitrate::program::AccountInfo]) -> Result<Context<Self>, solana_program::program_error::ProgramError> {
                     if accounts.len() < #expected {
                         return Err(solana_program::program_error::ProgramError::NotEnoughAccountKeys);
                     }
+                    #(#account_bindings)*
+
+                    // accounts are validated before any account is created
+                    #(#constraints)*
+
+                    #owners
+
+                    #(#inits)*
+
+                    #init_owners
+
+                    #(#reallocs)*
+
                     Ok(Context {
                         accounts: Self {
                             #(#account_fields,)*
                         },
                     })
                 }
+
+                #(#accessors)*
+
+                #close
+
+                pub fn account_by_name(&self, name: &str) -> Result<&'a nitrate::program::AccountInfo, solana_program::program_error::ProgramError> {
+                    match name {
+                        #(#named_accounts,)*
+                        _ => Err(solana_program::program_error::ProgramError::InvalidArgument),
+                    }
+                }
+            }
+        }
+    });
+
+    // maximum number of accounts across all instructions
+    let max_accounts = instructions.iter().map(|instruction| {
+        let (_, expected) = account_indices(&instruction.accounts);
+        quote! {
+            if #expected > max {
+                max = #expected;
+            }
+        }
+    });
+
+    // metadata of all instructions
+    let instruction_metadata = instructions.iter().map(|instruction| {
+        let name = syn::parse_str::<syn::Ident>(&instruction.name).unwrap();
+        let literal = &instruction.name;
+
+        quote! {
+            InstructionMetadata {
+                name: #literal,
+                accounts: #name::METADATA,
             }
         }
     });
 
     quote! {
+        pub const METADATA: &[InstructionMetadata] = &[#(#instruction_metadata,)*];
+
+        pub fn metadata(name: &str) -> Option<&'static InstructionMetadata> {
+            METADATA.iter().find(|instruction| instruction.name == name)
+        }
+
+        pub const MAX_ACCOUNTS: usize = {
+            let mut max = 0;
+            #(#max_accounts)*
+            max
+        };
+
         #(#instruction_structs)*
     }
 }
 
+/// Renders the owner checks of an instruction.
+///
+/// The owners of all accounts with an `owner` constraint are validated in a single
+/// pass, which logs the name of the offending account. Accounts with an `init`
+/// constraint only exist once they are created, so their owners are validated in
+/// a separate pass selected by `init`.
+fn render_owners(accounts: &[Account], init: bool) -> TokenStream {
+    let expected = accounts
+        .iter()
+        .filter(|account| account.init == init)
+        .filter_map(|account| {
+            let owner = account.owner.as_ref()?;
+            let account_name = syn::parse_str::<syn::Ident>(&account.name).unwrap();
+            let literal = &account.name;
+            let value = if account.optional {
+                quote!(#account_name)
+            } else {
+                quote!(Some(#account_name))
+            };
+
+            Some(quote! {
+                nitrate::program::owner::ExpectedOwner {
+                    name: #literal,
+                    account: #value,
+                    owner: &#owner,
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if expected.is_empty() {
+        return quote! {};
+    }
+
+    quote! {
+        nitrate::program::owner::check_owners(&[#(#expected,)*])?;
+    }
+}
+
+/// Renders the `close_accounts` method of an instruction.
+///
+/// The method closes every account with a `close` constraint. All accounts are bound
+/// to local variables so the recipient expressions can refer to other accounts of the
+/// instruction by their names.
+fn render_close(accounts: &[Account]) -> TokenStream {
+    let closes = accounts
+        .iter()
+        .filter_map(|account| {
+            let recipient = account.close.as_ref()?;
+            let account_name = syn::parse_str::<syn::Ident>(&account.name).unwrap();
+
+            Some(if account.optional {
+                quote! {
+                    if let Some(#account_name) = #account_name {
+                        #account_name.close(#recipient)?;
+                    }
+                }
+            } else {
+                quote! {
+                    #account_name.close(#recipient)?;
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    if closes.is_empty() {
+        return quote! {};
+    }
+
+    let account_fields = accounts
+        .iter()
+        .map(|account| syn::parse_str::<syn::Ident>(&account.name).unwrap());
+
+    quote! {
+        /// Closes the accounts with a `close` constraint.
+        ///
+        /// This is expected to be called at the end of the instruction processing,
+        /// once the accounts are no longer needed.
+        pub fn close_accounts(&self) -> solana_program::entrypoint::ProgramResult {
+            #[allow(unused_variables)]
+            let Self { #(#account_fields,)* } = self;
+
+            #(#closes)*
+
+            Ok(())
+        }
+    }
+}
+
+/// Renders the constraint checks of an account.
+///
+/// Checks are rendered as statements referring to the account by its name, so they
+/// can only be used after all accounts are bound. Optional accounts are only checked
+/// when they are present.
+fn render_constraints(account: &Account) -> TokenStream {
+    let account_name = syn::parse_str::<syn::Ident>(&account.name).unwrap();
+    let mut checks = Vec::new();
+
+    if account.program {
+        checks.push(quote! {
+            if !#account_name.executable() {
+                return Err(solana_program::program_error::ProgramError::IncorrectProgramId);
+            }
+        });
+
+        if let Some(address) = &account.address {
+            checks.push(quote! {
+                if #account_name.key() != &(#address) {
+                    return Err(solana_program::program_error::ProgramError::IncorrectProgramId);
+                }
+            });
+        }
+    }
+
+    if account.token_mint.is_some() || account.token_authority.is_some() {
+        let mut token_checks = Vec::new();
+
+        if let Some(mint) = &account.token_mint {
+            token_checks.push(quote! {
+                if token_account.mint() != #mint.key() {
+                    return Err(solana_program::program_error::ProgramError::InvalidAccountData);
+                }
+            });
+        }
+
+        if let Some(authority) = &account.token_authority {
+            token_checks.push(quote! {
+                if token_account.owner() != #authority.key() {
+                    return Err(solana_program::program_error::ProgramError::IllegalOwner);
+                }
+            });
+        }
+
+        // the token account reader checks the owner, account type and state
+        checks.push(quote! {
+            {
+                let token_account =
+                    nitrate::program::token::state::TokenAccount::try_from(#account_name)?;
+
+                #(#token_checks)*
+            }
+        });
+    }
+
+    if checks.is_empty() {
+        quote! {}
+    } else if account.optional {
+        quote! {
+            if let Some(#account_name) = #account_name {
+                #(#checks)*
+            }
+        }
+    } else {
+        quote! {
+            #(#checks)*
+        }
+    }
+}
+
+/// Renders the creation of an account with an `init` constraint.
+///
+/// Accounts are created after the constraints of all accounts are checked, so no
+/// state-changing CPI is performed for an instruction that fails validation.
+fn render_init(account: &Account) -> TokenStream {
+    if !account.init {
+        return quote! {};
+    }
+
+    let account_name = syn::parse_str::<syn::Ident>(&account.name).unwrap();
+    let payer = &account.payer;
+    let space = &account.space;
+
+    if let Some(seeds) = &account.seeds {
+        let count = seeds.len();
+
+        quote! {
+            {
+                let seeds: [&[u8]; #count] = [#((#seeds).as_ref(),)*];
+                let (address, bump) = solana_program::pubkey::Pubkey::find_program_address(&seeds, &crate::ID);
+
+                if #account_name.key() != &address {
+                    return Err(solana_program::program_error::ProgramError::InvalidSeeds);
+                }
+
+                nitrate::program::system::create_pda(#payer, #account_name, &seeds, bump, (#space) as u64, &crate::ID)?;
+            }
+        }
+    } else {
+        quote! {
+            {
+                nitrate::program::system::create_account_with_rent(#payer, #account_name, (#space) as u64, &crate::ID)?;
+            }
+        }
+    }
+}
+
+/// Renders the resize of an account with a `realloc` constraint.
+///
+/// Accounts are resized once all accounts are validated and created. Only the
+/// rent-exempt balance is settled with the payer: growing the account tops it up to
+/// the minimum balance of the new size, while shrinking it refunds the difference
+/// between the minimum balances of the old and new sizes. Lamports held above the
+/// rent-exempt balance are never moved.
+fn render_realloc(account: &Account) -> TokenStream {
+    let len = match &account.realloc {
+        Some(len) => len,
+        None => return quote! {},
+    };
+
+    let account_name = syn::parse_str::<syn::Ident>(&account.name).unwrap();
+    let payer = &account.realloc_payer;
+    let zero = account
+        .realloc_zero
+        .as_ref()
+        .map(|zero| quote!(#zero))
+        .unwrap_or(quote!(false));
+
+    quote! {
+        {
+            let len = (#len) as usize;
+            let current_len = #account_name.data_len();
+            let rent = <solana_program::rent::Rent as solana_program::sysvar::Sysvar>::get()?;
+            let minimum_balance = rent.minimum_balance(len);
+            let lamports = *#account_name.try_borrow_lamports()?;
+
+            if len > current_len {
+                // top up the account to the rent-exempt balance of the new size
+                if minimum_balance > lamports {
+                    nitrate::program::system::transfer(#payer, #account_name, minimum_balance - lamports)?;
+                }
+            } else if len < current_len {
+                // refund the rent released by the shrink, without going below the
+                // rent-exempt balance of the new size
+                let refund = rent
+                    .minimum_balance(current_len)
+                    .saturating_sub(minimum_balance)
+                    .min(lamports.saturating_sub(minimum_balance));
+
+                if refund > 0 {
+                    *#account_name.try_borrow_mut_lamports()? -= refund;
+                    *#payer.try_borrow_mut_lamports()? += refund;
+                }
+            }
+
+            #account_name.realloc(len, #zero)?;
+        }
+    }
+}
+
+/// Returns a human-readable representation of the constraints of an account.
+fn constraint_names(account: &Account) -> Vec<String> {
+    let mut constraints = Vec::new();
+
+    if account.group.is_some() {
+        constraints.push(GROUP_TOKEN.to_string());
+    }
+
+    if account.program {
+        constraints.push(PROGRAM_TOKEN.to_string());
+    }
+
+    if let Some(address) = &account.address {
+        constraints.push(format!(
+            "{ADDRESS_TOKEN} = {}",
+            expression(quote!(#address))
+        ));
+    }
+
+    if account.init {
+        constraints.push(INIT_TOKEN.to_string());
+    }
+
+    if let Some(payer) = &account.payer {
+        constraints.push(format!("{PAYER_TOKEN} = {}", expression(quote!(#payer))));
+    }
+
+    if let Some(space) = &account.space {
+        constraints.push(format!("{SPACE_TOKEN} = {}", expression(quote!(#space))));
+    }
+
+    if let Some(seeds) = &account.seeds {
+        constraints.push(format!(
+            "{SEEDS_TOKEN} = [{}]",
+            expression(quote!(#(#seeds),*))
+        ));
+    }
+
+    if let Some(owner) = &account.owner {
+        constraints.push(format!("{OWNER_TOKEN} = {}", expression(quote!(#owner))));
+    }
+
+    if let Some(len) = &account.realloc {
+        constraints.push(format!("{REALLOC_TOKEN} = {}", expression(quote!(#len))));
+    }
+
+    if let Some(payer) = &account.realloc_payer {
+        constraints.push(format!(
+            "{REALLOC_PAYER_TOKEN} = {}",
+            expression(quote!(#payer))
+        ));
+    }
+
+    if let Some(zero) = &account.realloc_zero {
+        constraints.push(format!(
+            "{REALLOC_ZERO_TOKEN} = {}",
+            expression(quote!(#zero))
+        ));
+    }
+
+    if let Some(recipient) = &account.close {
+        constraints.push(format!(
+            "{CLOSE_TOKEN} = {}",
+            expression(quote!(#recipient))
+        ));
+    }
+
+    if let Some(mint) = &account.token_mint {
+        constraints.push(format!(
+            "{TOKEN_MINT_TOKEN} = {}",
+            expression(quote!(#mint))
+        ));
+    }
+
+    if let Some(authority) = &account.token_authority {
+        constraints.push(format!(
+            "{TOKEN_AUTHORITY_TOKEN} = {}",
+            expression(quote!(#authority))
+        ));
+    }
+
+    constraints
+}
+
+/// Returns the source representation of an expression.
+///
+/// The token stream spacing is compacted around paths, method calls and
+/// separators to resemble the way the expression is written.
+fn expression(tokens: TokenStream) -> String {
+    tokens
+        .to_string()
+        .replace(" :: ", "::")
+        .replace(" . ", ".")
+        .replace(" ()", "()")
+        .replace(" ,", ",")
+}
+
+/// Returns the index expression of each account together with the total number of
+/// accounts.
+///
+/// The number of accounts of a group is only known when the program is compiled, so
+/// indices are rendered as an expression adding the length of preceding groups.
+fn account_indices(accounts: &[Account]) -> (Vec<TokenStream>, TokenStream) {
+    let mut count = 0usize;
+    let mut groups = Vec::new();
+
+    let indices = accounts
+        .iter()
+        .map(|account| {
+            let index = quote! { #count #(+ #groups::LEN)* };
+
+            if let Some(group) = &account.group {
+                groups.push(group.clone());
+            } else {
+                count += 1;
+            }
+
+            index
+        })
+        .collect();
+
+    (indices, quote! { #count #(+ #groups::LEN)* })
+}
+
 /// Internal representation of an instruction.
 #[derive(Default)]
 struct Instruction {
@@ -173,8 +877,72 @@ struct Instruction {
 }
 
 /// Internal representation of an account.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 struct Account {
     pub name: String,
     pub optional: bool,
+    pub signer: bool,
+    pub writable: bool,
+    /// Type of the group when the entry represents a group of accounts.
+    pub group: Option<syn::Path>,
+    /// Indicates whether the account must be an executable program.
+    pub program: bool,
+    /// Expected address (expression) of the program account.
+    pub address: Option<syn::Expr>,
+    /// Account (expression) expected as the mint of the token account.
+    pub token_mint: Option<syn::Expr>,
+    /// Account (expression) expected as the owner of the token account.
+    pub token_authority: Option<syn::Expr>,
+    /// Indicates whether the account is created when the context is created.
+    pub init: bool,
+    /// Account (expression) funding the creation of the account.
+    pub payer: Option<syn::Expr>,
+    /// Size (expression) of the account data.
+    pub space: Option<syn::Expr>,
+    /// Seeds (expressions) of the program derived address of the account.
+    pub seeds: Option<Vec<syn::Expr>>,
+    /// Account (expression) receiving the lamports when the account is closed.
+    pub close: Option<syn::Expr>,
+    /// New size (expression) of the account data.
+    pub realloc: Option<syn::Expr>,
+    /// Account (expression) paying for (or refunded by) the resize of the account.
+    pub realloc_payer: Option<syn::Expr>,
+    /// Indicates (expression) whether the new account data is zero-initialized.
+    pub realloc_zero: Option<syn::Expr>,
+    /// Program (expression) expected as the owner of the account.
+    pub owner: Option<syn::Expr>,
+}
+
+/// Property of an `#[account(...)]` attribute.
+struct Property {
+    /// Name of the property (`None` for a literal, e.g., the index of the account).
+    pub path: Option<syn::Path>,
+    /// Value of a name-value property (`None` for a flag, e.g., `signer`).
+    pub value: Option<syn::Expr>,
+}
+
+impl Parse for Property {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Lit) {
+            input.parse::<Lit>()?;
+            return Ok(Property {
+                path: None,
+                value: None,
+            });
+        }
+
+        let path = input.call(syn::Path::parse_mod_style)?;
+
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+
+        Ok(Property {
+            path: Some(path),
+            value,
+        })
+    }
 }
@@ -0,0 +1,78 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{self, DeriveInput, Error, Fields, GenericParam, Result, Type};
+
+/// Generates the account group implementation for a struct.
+pub fn generate_group(ast: DeriveInput) -> Result<TokenStream> {
+    let name = &ast.ident;
+
+    // the group must be generic over the lifetime of the accounts
+    let lifetime = ast
+        .generics
+        .params
+        .iter()
+        .find_map(|param| match param {
+            GenericParam::Lifetime(lifetime) => Some(lifetime.lifetime.clone()),
+            _ => None,
+        })
+        .ok_or(Error::new_spanned(
+            &ast.ident,
+            "account group must have a lifetime parameter",
+        ))?;
+
+    let fields = if let syn::Data::Struct(syn::DataStruct {
+        fields: Fields::Named(ref fields),
+        ..
+    }) = ast.data
+    {
+        &fields.named
+    } else {
+        return Err(Error::new_spanned(&ast, "no named fields found"));
+    };
+
+    // initialization
+    let account_fields = fields.iter().enumerate().map(|(index, field)| {
+        let account_name = field.ident.as_ref().unwrap();
+
+        if is_option(&field.ty) {
+            quote! {
+                #account_name: if accounts[#index].key() == &crate::ID { None } else { Some(&accounts[#index]) }
+            }
+        } else {
+            quote! {
+                #account_name: &accounts[#index]
+            }
+        }
+    });
+    // expected accounts
+    let expected = fields.len();
+
+    Ok(quote! {
+        impl<#lifetime> #name<#lifetime> {
+            /// Number of accounts in the group.
+            pub const LEN: usize = #expected;
+
+            /// Creates the group from the first `LEN` accounts of the slice.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the slice has less than `LEN` accounts.
+            #[inline(always)]
+            pub fn from_accounts(accounts: &#lifetime [nitrate::program::AccountInfo]) -> Self {
+                Self {
+                    #(#account_fields,)*
+                }
+            }
+        }
+    })
+}
+
+/// Indicates whether the type of the field is an `Option`.
+fn is_option(ty: &Type) -> bool {
+    if let Type::Path(syn::TypePath { path, .. }) = ty {
+        if let Some(segment) = path.segments.last() {
+            return segment.ident == "Option";
+        }
+    }
+    false
+}
@@ -1,5 +1,7 @@
 mod accounts;
+mod group;
 use accounts::generate_accounts;
+use group::generate_group;
 
 use proc_macro::TokenStream;
 use quote::quote;
@@ -20,7 +22,7 @@ use syn::{parse_macro_input, DeriveInput};
 ///
 /// # Examples
 ///
-/// ```no_run
+/// ```ignore
 /// #[derive(BorshDeserialize, BorshSerialize, Clone, Debug, ShankInstruction, Accounts)]
 /// pub struct Instruction {
 ///     /// Closes an uninitialized asset (buffer) account.
@@ -40,7 +42,7 @@ use syn::{parse_macro_input, DeriveInput};
 /// ```
 ///
 /// This will create a module `accounts` with a struct for each variant of the enum:
-/// ```no_run
+/// ```ignore
 /// use nitrate::program::AccountInfo;
 ///
 /// pub struct Close<'a> {
@@ -56,11 +58,113 @@ use syn::{parse_macro_input, DeriveInput};
 /// }
 /// ```
 /// A `Context` can then be created to access the accounts of an instruction:
-/// ```no_run
+/// ```ignore
 /// let ctx = Burn::context(accounts)?;
 /// msg!("Burn asset: {:?}", ctx.accounts.asset.key());
 /// ```
-#[proc_macro_derive(Accounts, attributes(account))]
+///
+/// Each struct also has an accessor method for every account and a fallible
+/// `account_by_name` to look up an account by its name:
+/// ```ignore
+/// let asset = ctx.accounts.asset();
+/// let recipient = ctx.accounts.account_by_name("recipient")?;
+/// ```
+///
+/// `account_by_name` returns `ProgramError::InvalidArgument` for an unknown name and
+/// `ProgramError::NotEnoughAccountKeys` for an optional account that was not
+/// provided. The names of the generated methods (`context`, `account_by_name` and
+/// `close_accounts`) are reserved: accounts with one of these names are only
+/// accessible through their field.
+///
+/// Accounts can also declare constraints, which are validated when the `Context` is
+/// created:
+///
+/// * `program`: the account must be an executable program; an `address` property
+///   can be used to also check the address of the program (e.g.,
+///   `#[account(2, program, address = spl_token::ID, name = "token_program")]`).
+///   Failing the constraint returns `ProgramError::IncorrectProgramId`.
+/// * `token::mint` and `token::authority`: the account must be a token account of
+///   the given mint and owner accounts, which refer to other accounts of the
+///   instruction (e.g., `#[account(3, token::mint = mint, name = "token")]`). The
+///   token account is read in place with `TokenAccount::try_from`, which returns
+///   `ProgramError::InvalidAccountOwner` for an account not owned by the SPL Token
+///   or Token-2022 program and `ProgramError::UninitializedAccount` for an
+///   uninitialized account; a mint mismatch returns `ProgramError::InvalidAccountData`
+///   and an owner mismatch returns `ProgramError::IllegalOwner`.
+/// * `owner`: the account must be owned by the given program, or by the program
+///   itself (`crate::ID`) when no value is specified (e.g., `#[account(1, owner, name
+///   = "state")]`). The owners of all accounts are validated in a single pass after
+///   the other constraints, returning `ProgramError::InvalidAccountOwner` for the
+///   first offending account (its name is logged when the `logging` feature is
+///   enabled).
+/// * `init`: the account is created, owned by the program (`crate::ID`), once the
+///   constraints of all other accounts are validated, so no account is created for
+///   an instruction that fails validation; the `owner` constraint of the account is
+///   validated after it is created. It requires a `payer` account and the `space` of
+///   the account data; the optional `seeds` create the account at a program derived
+///   address, returning `ProgramError::InvalidSeeds` if the address does not match
+///   (e.g., `#[account(1, writable, init, payer = funder, space = 40, seeds =
+///   [b"vault", funder.key()], name = "vault")]`).
+/// * `close`: the (writable) account can be closed into the given recipient account
+///   by calling the generated `close_accounts` method at the end of the instruction
+///   (e.g., `#[account(0, writable, close = recipient, name = "buffer")]`). Closing
+///   an account transfers its lamports, zeroes its data and assigns it to the System
+///   program.
+/// * `realloc`: the (writable) account is resized to the given length once all
+///   accounts are validated and created. Growing the account tops it up to the
+///   rent-exempt balance of the new size from the `realloc::payer` account, and
+///   shrinking it refunds the rent released by the smaller size to the payer;
+///   lamports held above the rent-exempt balance are left in the account.
+///   `realloc::zero` controls whether the new account data is zero-initialized
+///   (e.g., `#[account(1, writable, realloc = 8 + 4 * len, realloc::payer = funder,
+///   realloc::zero = true, name = "list")]`).
+///
+/// Accounts that are used together across instructions can be declared once as a
+/// group and included in a variant using a `#[group]` attribute, where `ty` is the
+/// path (relative to the generated `accounts` module) of a struct deriving
+/// [`AccountGroup`]. The group occupies as many positions as accounts it contains:
+/// ```ignore
+/// #[derive(AccountGroup)]
+/// pub struct TokenTriple<'a> {
+///     pub token_account: &'a AccountInfo,
+///     pub mint: &'a AccountInfo,
+///     pub token_program: &'a AccountInfo,
+/// }
+///
+/// #[derive(BorshDeserialize, BorshSerialize, Clone, Debug, Accounts)]
+/// pub enum Instruction {
+///     #[account(0, signer, name="authority", desc = "The authority")]
+///     #[group(name = "source", ty = "super::TokenTriple")]
+///     #[account(4, writable, name="recipient", desc = "The recipient account")]
+///     Withdraw,
+/// }
+///
+/// let ctx = Withdraw::context(accounts)?;
+/// msg!("Mint: {:?}", ctx.accounts.source.mint.key());
+/// ```
+///
+/// The account declarations are also available as metadata, which tooling can use to
+/// audit the account validation of each instruction. Each struct has a `METADATA`
+/// constant listing its accounts, and the module has a `METADATA` constant and a
+/// `metadata` function for the whole instruction set:
+/// ```ignore
+/// let burn = accounts::metadata("Burn").unwrap();
+///
+/// for account in burn.accounts {
+///     msg!("{} ({}): {:?}", account.name, account.index, account.constraints);
+/// }
+/// ```
+///
+/// The accounts of a group are represented by a single entry with a `group`
+/// constraint, which occupies `LEN` positions starting from its `index`.
+///
+/// The module also includes a `MAX_ACCOUNTS` constant with the maximum number of
+/// accounts declared across all instructions, which can be used as the maximum
+/// number of accounts of the entrypoint:
+/// ```ignore
+/// entrypoint!(process_instruction, accounts::MAX_ACCOUNTS);
+/// ```
+#[proc_macro_derive(Accounts, attributes(account, group))]
 pub fn context_derive(input: TokenStream) -> TokenStream {
     let ast = parse_macro_input!(input as DeriveInput);
     let accounts = generate_accounts(ast);
@@ -72,9 +176,57 @@ pub fn context_derive(input: TokenStream) -> TokenStream {
                     pub accounts: T,
                 }
 
+                #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+                pub struct AccountMetadata {
+                    pub name: &'static str,
+                    pub index: usize,
+                    pub signer: bool,
+                    pub writable: bool,
+                    pub optional: bool,
+                    pub constraints: &'static [&'static str],
+                }
+
+                #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+                pub struct InstructionMetadata {
+                    pub name: &'static str,
+                    pub accounts: &'static [AccountMetadata],
+                }
+
                 #accounts
             }
         }),
         Err(error) => error.to_compile_error().into(),
     }
 }
+
+/// Annotates a struct with `#[derive(AccountGroup)]` to use it as a reusable group
+/// of accounts in the `Accounts` derive.
+///
+/// The struct must have a lifetime parameter and each field must be either an
+/// `&AccountInfo` or, for optional accounts, an `Option<&AccountInfo>`. The order of
+/// the fields determines the order of the accounts in the group.
+///
+/// # Examples
+///
+/// ```ignore
+/// use nitrate::{program::AccountInfo, AccountGroup};
+///
+/// #[derive(AccountGroup)]
+/// pub struct TokenTriple<'a> {
+///     pub token_account: &'a AccountInfo,
+///     pub mint: &'a AccountInfo,
+///     pub token_program: &'a AccountInfo,
+/// }
+/// ```
+///
+/// This will add a `LEN` constant with the number of accounts in the group and a
+/// `from_accounts` function to create the group from a slice of accounts.
+#[proc_macro_derive(AccountGroup)]
+pub fn group_derive(input: TokenStream) -> TokenStream {
+    let ast = parse_macro_input!(input as DeriveInput);
+
+    match generate_group(ast) {
+        Ok(group) => TokenStream::from(group),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
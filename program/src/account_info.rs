@@ -17,9 +17,15 @@
 
 #![allow(clippy::missing_safety_doc)]
 
+use bytemuck::Pod;
 use solana_program::{
-    entrypoint::MAX_PERMITTED_DATA_INCREASE, program_error::ProgramError,
-    program_memory::sol_memset, pubkey::Pubkey,
+    entrypoint::MAX_PERMITTED_DATA_INCREASE,
+    program_error::ProgramError,
+    program_memory::{sol_memcpy, sol_memset},
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+    sysvar::Sysvar,
 };
 use std::{ptr::NonNull, slice::from_raw_parts_mut};
 
@@ -54,13 +60,10 @@ pub(crate) struct Account {
     /// Account's original data length when it was serialized for the
     /// current program invocation.
     ///
-    /// The value of this field is lazily initialized to the current data length.
-    /// On first access, the original data length will be 0. The caller should
-    /// ensure that the original data length is set to the current data length for
-    /// subsequence access.
-    ///
-    /// The value of this field is currently only used for `realloc`.
-    original_data_len: [u8; 4],
+    /// The value of this field is set to the data length when the input is
+    /// deserialized – the runtime serializes this field as padding – and it
+    /// determines the realloc region of the account.
+    pub(crate) original_data_len: [u8; 4],
 
     /// Public key of the account
     key: Pubkey,
@@ -75,20 +78,37 @@ pub(crate) struct Account {
     pub(crate) data_len: u64,
 }
 
-// Convenience macro to get the original data length from the account – the value will
-// be zero on first access.
+/// Offset of the account key in the serialized account.
+pub(crate) const KEY_OFFSET: usize = 8;
+
+/// Offset of the account owner in the serialized account.
+pub(crate) const OWNER_OFFSET: usize = 40;
+
+/// Offset of the account lamports in the serialized account.
+pub(crate) const LAMPORTS_OFFSET: usize = 72;
+
+/// Offset of the account data in the serialized account.
+pub(crate) const DATA_OFFSET: usize = 88;
+
+// The layout of `Account` must match the serialization of the runtime, since the
+// CPI structs point directly into the input buffer.
+const _: () = {
+    assert!(offset_of!(Account, borrow_state) == 0);
+    assert!(offset_of!(Account, is_signer) == 1);
+    assert!(offset_of!(Account, is_writable) == 2);
+    assert!(offset_of!(Account, executable) == 3);
+    assert!(offset_of!(Account, original_data_len) == 4);
+    assert!(offset_of!(Account, key) == KEY_OFFSET);
+    assert!(offset_of!(Account, owner) == OWNER_OFFSET);
+    assert!(offset_of!(Account, lamports) == LAMPORTS_OFFSET);
+    assert!(offset_of!(Account, data_len) == 80);
+    assert!(core::mem::size_of::<Account>() == DATA_OFFSET);
+};
+
+// Convenience macro to get the original data length from the account.
 macro_rules! get_original_data_len {
     ( $self:expr ) => {
-        unsafe { *(&(*$self).original_data_len as *const _ as *const u32) as usize }
-    };
-}
-
-// Convenience macro to set the original data length in the account.
-macro_rules! set_original_data_len {
-    ( $self:expr, $len:expr ) => {
-        unsafe {
-            *(&mut (*$self).original_data_len) = u32::to_le_bytes($len as u32);
-        }
+        unsafe { u32::from_le_bytes((*$self).original_data_len) as usize }
     };
 }
 
@@ -145,6 +165,15 @@ impl AccountInfo {
         unsafe { (*self.raw).data_len as usize }
     }
 
+    /// Returns the size of the data in the account when the program was invoked.
+    ///
+    /// The account data can be reallocated up to `original_data_len()` plus
+    /// `MAX_PERMITTED_DATA_INCREASE` bytes.
+    #[inline(always)]
+    pub fn original_data_len(&self) -> usize {
+        get_original_data_len!(self.raw)
+    }
+
     /// Indicates whether the account data is empty.
     ///
     /// An account is considered empty if the data length is zero.
@@ -208,7 +237,7 @@ impl AccountInfo {
 
     /// Tries to get a read-only reference to the lamport field, failing if the
     /// field is already mutable borrowed or if 7 borrows already exist.
-    pub fn try_borrow_lamports(&self) -> Result<Ref<u64>, ProgramError> {
+    pub fn try_borrow_lamports(&self) -> Result<Ref<'_, u64>, ProgramError> {
         let borrow_state = unsafe { &mut (*self.raw).borrow_state };
 
         // check if mutable borrow is already taken
@@ -234,7 +263,7 @@ impl AccountInfo {
 
     /// Tries to get a read only reference to the lamport field, failing if the field
     /// is already borrowed in any form.
-    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<u64>, ProgramError> {
+    pub fn try_borrow_mut_lamports(&self) -> Result<RefMut<'_, u64>, ProgramError> {
         let borrow_state = unsafe { &mut (*self.raw).borrow_state };
 
         // check if any borrow (mutable or immutable) is already taken for lamports
@@ -255,7 +284,7 @@ impl AccountInfo {
 
     /// Tries to get a read only reference to the data field, failing if the field
     /// is already mutable borrowed or if 7 borrows already exist.
-    pub fn try_borrow_data(&self) -> Result<Ref<[u8]>, ProgramError> {
+    pub fn try_borrow_data(&self) -> Result<Ref<'_, [u8]>, ProgramError> {
         let borrow_state = unsafe { &mut (*self.raw).borrow_state };
 
         // check if mutable data borrow is already taken (most significant bit
@@ -282,7 +311,7 @@ impl AccountInfo {
 
     /// Tries to get a read only reference to the data field, failing if the field
     /// is already borrowed in any form.
-    pub fn try_borrow_mut_data(&self) -> Result<RefMut<[u8]>, ProgramError> {
+    pub fn try_borrow_mut_data(&self) -> Result<RefMut<'_, [u8]>, ProgramError> {
         let borrow_state = unsafe { &mut (*self.raw).borrow_state };
 
         // check if any borrow (mutable or immutable) is already taken for data
@@ -301,6 +330,70 @@ impl AccountInfo {
         })
     }
 
+    /// Reads a value of type `T` at the given offset of the account data.
+    ///
+    /// This only checks that the data is not mutably borrowed, without creating
+    /// a reference to the whole data, which is more efficient than borrowing the
+    /// data to read a single field of a large account.
+    pub fn read_at<T: Pod>(&self, offset: usize) -> Result<T, ProgramError> {
+        let borrow_state = unsafe { (*self.raw).borrow_state };
+
+        // check if mutable data borrow is already taken
+        if borrow_state & 0b_0000_1000 != 0 {
+            return Err(ProgramError::AccountBorrowFailed);
+        }
+
+        self.check_bounds::<T>(offset)?;
+
+        Ok(unsafe { std::ptr::read_unaligned(self.data_ptr().add(offset) as *const T) })
+    }
+
+    /// Writes a value of type `T` at the given offset of the account data.
+    ///
+    /// This only checks that the data is not borrowed, without creating a mutable
+    /// reference to the whole data, which is more efficient than borrowing the data
+    /// to update a single field of a large account.
+    pub fn write_at<T: Pod>(&self, offset: usize, value: T) -> Result<(), ProgramError> {
+        let borrow_state = unsafe { (*self.raw).borrow_state };
+
+        // check if any borrow (mutable or immutable) is already taken for data
+        if borrow_state & 0b_0000_1111 != 0 {
+            return Err(ProgramError::AccountBorrowFailed);
+        }
+
+        self.check_bounds::<T>(offset)?;
+
+        unsafe { std::ptr::write_unaligned(self.data_ptr().add(offset) as *mut T, value) };
+
+        Ok(())
+    }
+
+    /// Tries to get a mutable reference to the spare capacity of the account data,
+    /// failing if the data is already borrowed in any form.
+    ///
+    /// The spare capacity is the region between the current data length and the
+    /// maximum length the account can be reallocated to. This region is zero-initialized
+    /// by the runtime, so it can be used as scratch memory without heap allocations.
+    ///
+    /// Note: The region is not zeroed again after use. Pass `true` for `zero_init` when
+    /// growing the account with [`AccountInfo::realloc`] after writing to it.
+    pub fn try_borrow_mut_spare_capacity(&self) -> Result<RefMut<'_, [u8]>, ProgramError> {
+        let mut data = self.try_borrow_mut_data()?;
+
+        let capacity = self.original_data_len() + MAX_PERMITTED_DATA_INCREASE;
+        let len = capacity.saturating_sub(data.len());
+
+        let spare = RefMut {
+            value: unsafe { from_raw_parts_mut(data.as_mut_ptr().add(data.len()), len) },
+            state: data.state,
+            borrow_mask: data.borrow_mask,
+        };
+        // the data borrow is transferred to the spare capacity reference
+        std::mem::forget(data);
+
+        Ok(spare)
+    }
+
     /// Realloc the account's data and optionally zero-initialize the new
     /// memory.
     ///
@@ -328,13 +421,7 @@ impl AccountInfo {
             return Ok(());
         }
 
-        let original_len = match get_original_data_len!(self.raw) {
-            len if len > 0 => len,
-            _ => {
-                set_original_data_len!(self.raw, current_len);
-                current_len
-            }
-        };
+        let original_len = get_original_data_len!(self.raw);
 
         // return early if the length increase from the original serialized data
         // length is too large and would result in an out of bounds allocation
@@ -361,6 +448,101 @@ impl AccountInfo {
         Ok(())
     }
 
+    /// Replaces the account data with the given bytes.
+    ///
+    /// The account is resized to the length of `data` if needed, which is subject
+    /// to the same limits as [`AccountInfo::realloc`].
+    pub fn replace_data(&self, data: &[u8]) -> Result<(), ProgramError> {
+        // the new data overwrites the whole account data, so there is no need
+        // to zero-initialize any new memory
+        self.realloc(data.len(), false)?;
+
+        let mut account_data = self.try_borrow_mut_data()?;
+        sol_memcpy(&mut account_data, data, data.len());
+
+        Ok(())
+    }
+
+    /// Copies the data of the `source` account into this account.
+    ///
+    /// The account is resized to the length of the `source` data if needed, which
+    /// is subject to the same limits as [`AccountInfo::realloc`]. This is useful to
+    /// promote a staging buffer into a live account.
+    pub fn copy_data_from(&self, source: &AccountInfo) -> Result<(), ProgramError> {
+        // nothing to copy when both infos refer to the same account
+        if self.raw == source.raw {
+            return Ok(());
+        }
+
+        let source_data = source.try_borrow_data()?;
+        self.replace_data(&source_data)
+    }
+
+    /// Indicates whether the account holds enough lamports to be rent-exempt for
+    /// its current data length.
+    ///
+    /// The minimum balance is read from the `Rent` sysvar through its syscall.
+    pub fn is_rent_exempt(&self) -> Result<bool, ProgramError> {
+        let minimum_balance = Rent::get()?.minimum_balance(self.data_len());
+        Ok(*self.try_borrow_lamports()? >= minimum_balance)
+    }
+
+    /// Checks that the account holds enough lamports to be rent-exempt for its
+    /// current data length.
+    ///
+    /// Returns `ProgramError::AccountNotRentExempt` if the account is underfunded.
+    pub fn assert_rent_exempt(&self) -> Result<(), ProgramError> {
+        if !self.is_rent_exempt()? {
+            #[cfg(feature = "logging")]
+            solana_program::msg!("🔴 Account {} is not rent-exempt", self.key());
+            return Err(ProgramError::AccountNotRentExempt);
+        }
+
+        Ok(())
+    }
+
+    /// Closes the account, transferring its lamports to the `recipient`.
+    ///
+    /// The account data is zeroed and resized to zero, and the account is assigned
+    /// to the System program, so the account is garbage collected at the end of the
+    /// transaction and cannot be revived with stale data.
+    pub fn close(&self, recipient: &AccountInfo) -> Result<(), ProgramError> {
+        // closing an account into itself would burn its lamports
+        if self.raw == recipient.raw {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        {
+            let mut lamports = self.try_borrow_mut_lamports()?;
+            let mut recipient_lamports = recipient.try_borrow_mut_lamports()?;
+
+            *recipient_lamports = recipient_lamports
+                .checked_add(*lamports)
+                .ok_or(ProgramError::ArithmeticOverflow)?;
+            *lamports = 0;
+        }
+
+        {
+            let mut data = self.try_borrow_mut_data()?;
+            let len = data.len();
+            sol_memset(&mut data, 0, len);
+        }
+
+        self.realloc(0, false)?;
+        self.assign(&system_program::ID);
+
+        Ok(())
+    }
+
+    /// Checks that a value of type `T` at the given offset fits in the account data.
+    #[inline(always)]
+    fn check_bounds<T>(&self, offset: usize) -> Result<(), ProgramError> {
+        match offset.checked_add(std::mem::size_of::<T>()) {
+            Some(end) if end <= self.data_len() => Ok(()),
+            _ => Err(ProgramError::AccountDataTooSmall),
+        }
+    }
+
     /// Returns the memory address of the account data.
     fn data_ptr(&self) -> *mut u8 {
         unsafe { (self.raw as *const _ as *mut u8).add(std::mem::size_of::<Account>()) }
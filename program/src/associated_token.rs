@@ -0,0 +1,183 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Associated Token Account Program CPI functions.
+
+use solana_program::{entrypoint::ProgramResult, pubkey, pubkey::Pubkey};
+
+use crate::{
+    cpi::{self, invoke_signed_c, CAccountInfo, CAccountMeta, CInstruction, CSigner},
+    AccountInfo,
+};
+
+/// Associated Token Account program id.
+pub const ID: Pubkey = pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+/// Create an associated token account.
+///
+/// The instruction fails if the associated token account already exists.
+///
+/// # Arguments
+///
+/// * `payer`: Account funding the associated token account.
+/// * `ata`: Associated token account to create.
+/// * `owner`: Owner of the associated token account.
+/// * `mint`: Mint of the associated token account.
+/// * `system_program`: System program account.
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+#[inline(always)]
+pub fn create(
+    payer: &AccountInfo,
+    ata: &AccountInfo,
+    owner: &AccountInfo,
+    mint: &AccountInfo,
+    system_program: &AccountInfo,
+    token_program: &AccountInfo,
+) -> ProgramResult {
+    // create instruction has a '0' discriminator
+    _create_signed(
+        payer,
+        ata,
+        owner,
+        mint,
+        system_program,
+        token_program,
+        0,
+        &[],
+    )
+}
+
+/// Create an associated token account if it does not exist.
+///
+/// The instruction succeeds without changes if the associated token account
+/// already exists.
+///
+/// # Arguments
+///
+/// * `payer`: Account funding the associated token account.
+/// * `ata`: Associated token account to create.
+/// * `owner`: Owner of the associated token account.
+/// * `mint`: Mint of the associated token account.
+/// * `system_program`: System program account.
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+#[inline(always)]
+pub fn create_idempotent(
+    payer: &AccountInfo,
+    ata: &AccountInfo,
+    owner: &AccountInfo,
+    mint: &AccountInfo,
+    system_program: &AccountInfo,
+    token_program: &AccountInfo,
+) -> ProgramResult {
+    // create idempotent instruction has a '1' discriminator
+    _create_signed(
+        payer,
+        ata,
+        owner,
+        mint,
+        system_program,
+        token_program,
+        1,
+        &[],
+    )
+}
+
+/// Create an associated token account if it does not exist, with a program
+/// signed instruction.
+///
+/// This is used when the payer is a program derived address.
+///
+/// # Arguments
+///
+/// * `payer`: Account funding the associated token account.
+/// * `ata`: Associated token account to create.
+/// * `owner`: Owner of the associated token account.
+/// * `mint`: Mint of the associated token account.
+/// * `system_program`: System program account.
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `signer_seeds`: Seeds used to sign the instruction.
+#[inline(always)]
+pub fn create_idempotent_signed<const SEEDS: usize>(
+    payer: &AccountInfo,
+    ata: &AccountInfo,
+    owner: &AccountInfo,
+    mint: &AccountInfo,
+    system_program: &AccountInfo,
+    token_program: &AccountInfo,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    // create idempotent instruction has a '1' discriminator
+    _create_signed(
+        payer,
+        ata,
+        owner,
+        mint,
+        system_program,
+        token_program,
+        1,
+        &signer,
+    )
+}
+
+//-- Internal functions
+
+/// Create an associated token account either with or without a program signed
+/// instruction.
+#[allow(clippy::too_many_arguments)]
+fn _create_signed(
+    payer: &AccountInfo,
+    ata: &AccountInfo,
+    owner: &AccountInfo,
+    mint: &AccountInfo,
+    system_program: &AccountInfo,
+    token_program: &AccountInfo,
+    discriminator: u8,
+    signer: &[CSigner],
+) -> ProgramResult {
+    let instruction_accounts: [CAccountMeta; 6] = [
+        CAccountMeta::writable_signer(payer.key()),
+        CAccountMeta::writable(ata.key()),
+        CAccountMeta::readonly(owner.key()),
+        CAccountMeta::readonly(mint.key()),
+        CAccountMeta::readonly(system_program.key()),
+        CAccountMeta::readonly(token_program.key()),
+    ];
+
+    let instruction_data = [discriminator];
+
+    let instruction = CInstruction {
+        program_id: &ID,
+        accounts: instruction_accounts.as_ptr(),
+        accounts_len: instruction_accounts.len() as u64,
+        data: instruction_data.as_ptr(),
+        data_len: instruction_data.len() as u64,
+    };
+
+    let account_infos: [CAccountInfo; 6] = [
+        payer.into(),
+        ata.into(),
+        owner.into(),
+        mint.into(),
+        system_program.into(),
+        token_program.into(),
+    ];
+
+    invoke_signed_c(&instruction, &account_infos, signer)
+}
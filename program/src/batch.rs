@@ -0,0 +1,115 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers to process batched instructions.
+//!
+//! A batch is represented by instruction data containing a sequence of frames,
+//! where each frame is a little-endian `u16` length followed by the payload of
+//! a single (logical) instruction.
+
+use solana_program::{entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::AccountInfo;
+
+/// Size of the frame length prefix.
+pub const FRAME_HEADER_LEN: usize = std::mem::size_of::<u16>();
+
+/// Processes each frame of the instruction data as a separate instruction.
+///
+/// All frames share the same accounts. Processing stops at the first error,
+/// which is returned; a truncated frame results in an
+/// `ProgramError::InvalidInstructionData` error.
+///
+/// # Arguments
+///
+/// * `program_id`: Program id of the program.
+/// * `accounts`: Accounts of the instruction.
+/// * `instruction_data`: Instruction data containing the frames.
+/// * `process_instruction`: Function to process each frame.
+///
+/// # Examples
+///
+/// ```ignore
+/// entrypoint!(process_batch, 10);
+///
+/// pub fn process_batch(
+///     program_id: &Pubkey,
+///     accounts: &[AccountInfo],
+///     instruction_data: &[u8],
+/// ) -> ProgramResult {
+///     batch::process(program_id, accounts, instruction_data, process_instruction)
+/// }
+/// ```
+#[inline(always)]
+pub fn process<F>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+    process_instruction: F,
+) -> ProgramResult
+where
+    F: Fn(&Pubkey, &[AccountInfo], &[u8]) -> ProgramResult,
+{
+    for frame in frames(instruction_data) {
+        process_instruction(program_id, accounts, frame?)?;
+    }
+
+    Ok(())
+}
+
+/// Returns an iterator over the frames of the instruction data.
+#[inline(always)]
+pub fn frames(instruction_data: &[u8]) -> Frames<'_> {
+    Frames {
+        data: instruction_data,
+    }
+}
+
+/// Iterator over the frames of a batch.
+pub struct Frames<'a> {
+    /// Remaining data to parse.
+    data: &'a [u8],
+}
+
+impl<'a> Iterator for Frames<'a> {
+    type Item = Result<&'a [u8], ProgramError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let frame = self
+            .data
+            .get(..FRAME_HEADER_LEN)
+            .map(|header| u16::from_le_bytes([header[0], header[1]]) as usize + FRAME_HEADER_LEN)
+            .and_then(|end| {
+                self.data
+                    .get(FRAME_HEADER_LEN..end)
+                    .map(|frame| (frame, end))
+            });
+
+        match frame {
+            Some((frame, end)) => {
+                self.data = &self.data[end..];
+                Some(Ok(frame))
+            }
+            None => {
+                // stops the iteration on a truncated frame
+                self.data = &[];
+                Some(Err(ProgramError::InvalidInstructionData))
+            }
+        }
+    }
+}
@@ -0,0 +1,138 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Compute Budget program introspection.
+
+use solana_program::{
+    compute_units::sol_remaining_compute_units, entrypoint::ProgramResult,
+    program_error::ProgramError, pubkey, pubkey::Pubkey,
+};
+
+use crate::sysvars::instructions::Instructions;
+
+/// Compute Budget program id.
+pub const ID: Pubkey = pubkey!("ComputeBudget111111111111111111111111111111");
+
+/// Custom error code returned when the remaining compute units are below the
+/// required minimum.
+///
+/// The code is at the top of the `u32` range to avoid conflicts with the error
+/// codes of programs.
+pub const INSUFFICIENT_COMPUTE_UNITS: u32 = u32::MAX - 1;
+
+/// Compute budget requested by the instructions of the transaction.
+///
+/// Each field is `None` when the transaction does not include the corresponding
+/// instruction, in which case the runtime uses its default value.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ComputeBudgetRequest {
+    /// Requested heap frame size (in bytes).
+    pub heap_frame: Option<u32>,
+
+    /// Requested compute unit limit.
+    pub compute_unit_limit: Option<u32>,
+
+    /// Requested compute unit price (in micro-lamports), used to determine the
+    /// prioritization fee.
+    pub compute_unit_price: Option<u64>,
+
+    /// Requested limit of the loaded accounts data size (in bytes).
+    pub loaded_accounts_data_size_limit: Option<u32>,
+}
+
+/// Returns the compute budget requested by the instructions of the transaction.
+///
+/// Returns `ProgramError::InvalidInstructionData` if a Compute Budget instruction
+/// cannot be parsed.
+///
+/// # Arguments
+///
+/// * `instructions`: Instructions sysvar.
+pub fn requested(instructions: &Instructions) -> Result<ComputeBudgetRequest, ProgramError> {
+    let mut request = ComputeBudgetRequest::default();
+
+    for index in 0..instructions.num_instructions() {
+        let instruction = instructions.load_instruction_at(index)?;
+
+        if instruction.program_id() != &ID {
+            continue;
+        }
+
+        // -    0: instruction discriminator
+        // - 1..5: u32 value (u64 for the compute unit price)
+        match instruction.data() {
+            [1, value @ ..] => request.heap_frame = Some(read_u32(value)?),
+            [2, value @ ..] => request.compute_unit_limit = Some(read_u32(value)?),
+            [3, value @ ..] => {
+                let value = value.get(..8).ok_or(ProgramError::InvalidInstructionData)?;
+                request.compute_unit_price = Some(u64::from_le_bytes(value.try_into().unwrap()));
+            }
+            [4, value @ ..] => request.loaded_accounts_data_size_limit = Some(read_u32(value)?),
+            _ => return Err(ProgramError::InvalidInstructionData),
+        }
+    }
+
+    Ok(request)
+}
+
+/// Returns the compute unit limit requested by the transaction.
+///
+/// # Arguments
+///
+/// * `instructions`: Instructions sysvar.
+#[inline(always)]
+pub fn requested_compute_unit_limit(
+    instructions: &Instructions,
+) -> Result<Option<u32>, ProgramError> {
+    requested(instructions).map(|request| request.compute_unit_limit)
+}
+
+/// Returns the compute unit price (in micro-lamports) requested by the transaction.
+///
+/// # Arguments
+///
+/// * `instructions`: Instructions sysvar.
+#[inline(always)]
+pub fn requested_compute_unit_price(
+    instructions: &Instructions,
+) -> Result<Option<u64>, ProgramError> {
+    requested(instructions).map(|request| request.compute_unit_price)
+}
+
+/// Checks that the remaining compute units are at least `minimum`.
+///
+/// Returns `ProgramError::Custom(INSUFFICIENT_COMPUTE_UNITS)` otherwise, which allows
+/// an instruction to fail before any state is modified instead of running out of
+/// compute units midway.
+///
+/// # Arguments
+///
+/// * `minimum`: Minimum number of compute units required.
+#[inline(always)]
+pub fn require_remaining(minimum: u64) -> ProgramResult {
+    if sol_remaining_compute_units() < minimum {
+        return Err(ProgramError::Custom(INSUFFICIENT_COMPUTE_UNITS));
+    }
+
+    Ok(())
+}
+
+/// Reads a little-endian `u32` value.
+#[inline(always)]
+fn read_u32(value: &[u8]) -> Result<u32, ProgramError> {
+    value
+        .get(..4)
+        .map(|value| u32::from_le_bytes(value.try_into().unwrap()))
+        .ok_or(ProgramError::InvalidInstructionData)
+}
@@ -15,9 +15,27 @@
 
 //! Cross-program invocation helper types.
 
-use solana_program::pubkey::Pubkey;
+use std::mem::MaybeUninit;
 
-use crate::account_info::AccountInfo;
+use bytemuck::Pod;
+
+use solana_program::{
+    entrypoint::{ProgramResult, SUCCESS},
+    instruction::{
+        get_stack_height, AccountMeta, Instruction, ProcessedSiblingInstruction,
+        TRANSACTION_LEVEL_STACK_HEIGHT,
+    },
+    program::MAX_RETURN_DATA,
+    program_error::ProgramError,
+    pubkey::{Pubkey, MAX_SEEDS},
+    syscalls::{MAX_CPI_ACCOUNT_INFOS, MAX_CPI_INSTRUCTION_ACCOUNTS, MAX_CPI_INSTRUCTION_DATA_LEN},
+};
+
+use crate::{
+    account_info::{AccountInfo, DATA_OFFSET, KEY_OFFSET, LAMPORTS_OFFSET, OWNER_OFFSET},
+    roles::{Program, Signer, Writable},
+    sysvars::instructions::Instructions,
+};
 
 /// An `AccountMeta`` as expected by `sol_invoke_signed_c`.
 #[repr(C)]
@@ -33,10 +51,83 @@ pub struct CAccountMeta {
     pub is_signer: bool,
 }
 
+impl CAccountMeta {
+    /// Creates an account meta from the public key of the account.
+    ///
+    /// This allows creating the metas of an instruction without the `AccountInfo`
+    /// of the accounts (e.g., sharing the instruction construction with off-chain
+    /// code).
+    ///
+    /// # Arguments
+    ///
+    /// * `pubkey`: Public key of the account.
+    /// * `is_writable`: Whether the account is writable.
+    /// * `is_signer`: Whether the account signs the instruction.
+    #[inline(always)]
+    pub fn new(pubkey: &Pubkey, is_writable: bool, is_signer: bool) -> Self {
+        CAccountMeta {
+            pubkey,
+            is_writable,
+            is_signer,
+        }
+    }
+
+    /// Creates a read-only account meta.
+    ///
+    /// # Arguments
+    ///
+    /// * `pubkey`: Public key of the account.
+    #[inline(always)]
+    pub fn readonly(pubkey: &Pubkey) -> Self {
+        Self::new(pubkey, false, false)
+    }
+
+    /// Creates a writable account meta.
+    ///
+    /// # Arguments
+    ///
+    /// * `pubkey`: Public key of the account.
+    #[inline(always)]
+    pub fn writable(pubkey: &Pubkey) -> Self {
+        Self::new(pubkey, true, false)
+    }
+
+    /// Creates a read-only signer account meta.
+    ///
+    /// # Arguments
+    ///
+    /// * `pubkey`: Public key of the account.
+    #[inline(always)]
+    pub fn readonly_signer(pubkey: &Pubkey) -> Self {
+        Self::new(pubkey, false, true)
+    }
+
+    /// Creates a writable signer account meta.
+    ///
+    /// # Arguments
+    ///
+    /// * `pubkey`: Public key of the account.
+    #[inline(always)]
+    pub fn writable_signer(pubkey: &Pubkey) -> Self {
+        Self::new(pubkey, true, true)
+    }
+}
+
+impl From<(&AccountInfo, bool, bool)> for CAccountMeta {
+    /// Creates an account meta from an account and its writable and signer flags.
+    fn from((account, is_writable, is_signer): (&AccountInfo, bool, bool)) -> Self {
+        CAccountMeta {
+            pubkey: offset(account.raw, KEY_OFFSET),
+            is_writable,
+            is_signer,
+        }
+    }
+}
+
 impl From<&AccountInfo> for CAccountMeta {
     fn from(account: &AccountInfo) -> Self {
         CAccountMeta {
-            pubkey: offset(account.raw, 8),
+            pubkey: offset(account.raw, KEY_OFFSET),
             is_writable: account.is_writable(),
             is_signer: account.is_signer(),
         }
@@ -83,11 +174,11 @@ const fn offset<T, U>(ptr: *const T, offset: usize) -> *const U {
 impl From<&AccountInfo> for CAccountInfo {
     fn from(account: &AccountInfo) -> Self {
         CAccountInfo {
-            key: offset(account.raw, 8),
-            lamports: offset(account.raw, 72),
+            key: offset(account.raw, KEY_OFFSET),
+            lamports: offset(account.raw, LAMPORTS_OFFSET),
             data_len: account.data_len() as u64,
-            data: offset(account.raw, 88),
-            owner: offset(account.raw, 40),
+            data: offset(account.raw, DATA_OFFSET),
+            owner: offset(account.raw, OWNER_OFFSET),
             rent_epoch: 0,
             is_signer: account.is_signer(),
             is_writable: account.is_writable(),
@@ -96,6 +187,100 @@ impl From<&AccountInfo> for CAccountInfo {
     }
 }
 
+/// Account that signs a cross-program instruction.
+///
+/// The account meta of the account is always a signer, which is required for
+/// accounts signing the instruction with seeds (e.g., a program derived address).
+#[derive(Clone, Copy)]
+pub struct AsSigner<'a>(pub &'a AccountInfo);
+
+/// Trait for types that can be converted to an account meta.
+///
+/// The writable and signer flags of the meta are taken from the account, unless the
+/// type encodes the privilege (e.g., a `Signer` is always a signer).
+pub trait ToAccountMeta {
+    /// Returns the account meta of the account.
+    fn to_account_meta(&self) -> CAccountMeta;
+}
+
+impl ToAccountMeta for &AccountInfo {
+    #[inline(always)]
+    fn to_account_meta(&self) -> CAccountMeta {
+        CAccountMeta::from(*self)
+    }
+}
+
+impl ToAccountMeta for AsSigner<'_> {
+    #[inline(always)]
+    fn to_account_meta(&self) -> CAccountMeta {
+        CAccountMeta {
+            is_signer: true,
+            ..CAccountMeta::from(self.0)
+        }
+    }
+}
+
+impl ToAccountMeta for Signer<'_> {
+    #[inline(always)]
+    fn to_account_meta(&self) -> CAccountMeta {
+        CAccountMeta {
+            is_signer: true,
+            ..CAccountMeta::from(self.account())
+        }
+    }
+}
+
+impl ToAccountMeta for Writable<'_> {
+    #[inline(always)]
+    fn to_account_meta(&self) -> CAccountMeta {
+        CAccountMeta {
+            is_writable: true,
+            ..CAccountMeta::from(self.account())
+        }
+    }
+}
+
+impl ToAccountMeta for Program<'_> {
+    #[inline(always)]
+    fn to_account_meta(&self) -> CAccountMeta {
+        CAccountMeta::from(self.account())
+    }
+}
+
+/// Trait for tuples of accounts that can be converted to the account metas of an
+/// instruction.
+///
+/// # Examples
+///
+/// ```ignore
+/// // the new account must sign the instruction
+/// let metas = (funder, AsSigner(account)).into_account_metas();
+/// ```
+pub trait IntoAccountMetas<const N: usize> {
+    /// Returns the account metas of the accounts.
+    fn into_account_metas(self) -> [CAccountMeta; N];
+}
+
+macro_rules! impl_into_account_metas {
+    ( $n:literal; $( $name:ident: $index:tt ),+ ) => {
+        impl<$( $name: ToAccountMeta ),+> IntoAccountMetas<$n> for ($( $name, )+) {
+            #[inline(always)]
+            fn into_account_metas(self) -> [CAccountMeta; $n] {
+                [$( self.$index.to_account_meta() ),+]
+            }
+        }
+    };
+}
+
+impl_into_account_metas!(1; A: 0);
+impl_into_account_metas!(2; A: 0, B: 1);
+impl_into_account_metas!(3; A: 0, B: 1, C: 2);
+impl_into_account_metas!(4; A: 0, B: 1, C: 2, D: 3);
+impl_into_account_metas!(5; A: 0, B: 1, C: 2, D: 3, E: 4);
+impl_into_account_metas!(6; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5);
+impl_into_account_metas!(7; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6);
+impl_into_account_metas!(8; A: 0, B: 1, C: 2, D: 3, E: 4, F: 5, G: 6, H: 7);
+
 /*
 impl CAccountInfo {
     /// A CPI utility function
@@ -141,6 +326,51 @@ pub struct CInstruction {
     pub data_len: u64,
 }
 
+impl CInstruction {
+    /// Creates an instruction from a `solana_program` instruction.
+    ///
+    /// The account metas of the instruction are written to the `metas` buffer, so
+    /// the instruction and the buffer must outlive the returned instruction. This
+    /// allows invoking instructions created by existing instruction builders.
+    ///
+    /// Returns `ProgramError::InvalidArgument` if the buffer is smaller than the
+    /// number of accounts of the instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `instruction`: Instruction to convert.
+    /// * `metas`: Buffer for the account metas of the instruction.
+    #[inline(always)]
+    pub fn from_instruction(
+        instruction: &Instruction,
+        metas: &mut [MaybeUninit<CAccountMeta>],
+    ) -> Result<Self, ProgramError> {
+        if metas.len() < instruction.accounts.len() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        instruction
+            .accounts
+            .iter()
+            .zip(metas.iter_mut())
+            .for_each(|(account, meta)| {
+                meta.write(CAccountMeta::new(
+                    &account.pubkey,
+                    account.is_writable,
+                    account.is_signer,
+                ));
+            });
+
+        Ok(CInstruction {
+            program_id: &instruction.program_id,
+            accounts: metas.as_ptr() as *const CAccountMeta,
+            accounts_len: instruction.accounts.len() as u64,
+            data: instruction.data.as_ptr(),
+            data_len: instruction.data.len() as u64,
+        })
+    }
+}
+
 /// A signer seed as expected by `sol_invoke_signed_c`.
 #[repr(C)]
 #[derive(Debug, PartialEq, Clone)]
@@ -162,3 +392,1040 @@ pub struct CSigner {
     /// Number of signers.
     pub len: u64,
 }
+
+// The layout of the CPI structs must match the C ABI of `sol_invoke_signed_c`.
+#[cfg(target_pointer_width = "64")]
+const _: () = {
+    assert!(offset_of!(CAccountMeta, pubkey) == 0);
+    assert!(offset_of!(CAccountMeta, is_writable) == 8);
+    assert!(offset_of!(CAccountMeta, is_signer) == 9);
+    assert!(core::mem::size_of::<CAccountMeta>() == 16);
+
+    assert!(offset_of!(CAccountInfo, key) == 0);
+    assert!(offset_of!(CAccountInfo, lamports) == 8);
+    assert!(offset_of!(CAccountInfo, data_len) == 16);
+    assert!(offset_of!(CAccountInfo, data) == 24);
+    assert!(offset_of!(CAccountInfo, owner) == 32);
+    assert!(offset_of!(CAccountInfo, rent_epoch) == 40);
+    assert!(offset_of!(CAccountInfo, is_signer) == 48);
+    assert!(offset_of!(CAccountInfo, is_writable) == 49);
+    assert!(offset_of!(CAccountInfo, executable) == 50);
+    assert!(core::mem::size_of::<CAccountInfo>() == 56);
+
+    assert!(offset_of!(CInstruction, program_id) == 0);
+    assert!(offset_of!(CInstruction, accounts) == 8);
+    assert!(offset_of!(CInstruction, accounts_len) == 16);
+    assert!(offset_of!(CInstruction, data) == 24);
+    assert!(offset_of!(CInstruction, data_len) == 32);
+    assert!(core::mem::size_of::<CInstruction>() == 40);
+
+    assert!(core::mem::size_of::<CSignerSeed>() == 16);
+    assert!(core::mem::size_of::<CSigner>() == 16);
+};
+
+/// Returns the current stack height.
+///
+/// Transaction-level instructions have a height of `TRANSACTION_LEVEL_STACK_HEIGHT`,
+/// the first invoked inner instruction has a height of `TRANSACTION_LEVEL_STACK_HEIGHT + 1`,
+/// and so on.
+#[inline(always)]
+pub fn stack_height() -> usize {
+    get_stack_height()
+}
+
+/// Returns the number of nested CPIs of the current instruction.
+///
+/// Transaction-level instructions have a depth of `0`, the first invoked inner
+/// instruction has a depth of `1`, and so on.
+#[inline(always)]
+pub fn cpi_depth() -> usize {
+    stack_height().saturating_sub(TRANSACTION_LEVEL_STACK_HEIGHT)
+}
+
+/// Rejects the execution when the program is invoked through a CPI.
+///
+/// Returns `ProgramError::IncorrectProgramId` when the instruction is not a
+/// transaction-level instruction.
+#[inline(always)]
+pub fn assert_top_level() -> ProgramResult {
+    assert_max_depth(0)
+}
+
+/// Rejects the execution when the program is invoked through more than `depth`
+/// nested CPIs.
+///
+/// Returns `ProgramError::IncorrectProgramId` when the depth is exceeded.
+///
+/// # Arguments
+///
+/// * `depth`: Maximum number of nested CPIs.
+#[inline(always)]
+pub fn assert_max_depth(depth: usize) -> ProgramResult {
+    let current = cpi_depth();
+
+    if current > depth {
+        #[cfg(feature = "logging")]
+        solana_program::msg!("🔴 CPI depth {} exceeds the maximum of {}", current, depth);
+
+        Err(ProgramError::IncorrectProgramId)
+    } else {
+        Ok(())
+    }
+}
+
+/// Rejects the execution when the program is invoked through a CPI from a program
+/// not in the list of allowed callers.
+///
+/// The runtime only exposes the outermost program of the call stack (the program
+/// of the transaction-level instruction), so the guard rejects any nested execution
+/// where the outermost program is not one of the `allowed_callers`. This includes
+/// the program itself, which prevents it from being re-entered unless it is
+/// explicitly listed.
+///
+/// Returns `ProgramError::IncorrectProgramId` when the caller is not allowed.
+///
+/// # Arguments
+///
+/// * `instructions_sysvar`: Instructions sysvar account.
+/// * `allowed_callers`: Programs allowed to invoke the program through a CPI.
+pub fn check_reentrancy(
+    instructions_sysvar: &AccountInfo,
+    allowed_callers: &[Pubkey],
+) -> ProgramResult {
+    if stack_height() <= TRANSACTION_LEVEL_STACK_HEIGHT {
+        return Ok(());
+    }
+
+    let instructions = Instructions::try_from(instructions_sysvar)?;
+    let caller = instructions.load_current_instruction()?.program_id();
+
+    if allowed_callers.contains(caller) {
+        Ok(())
+    } else {
+        #[cfg(feature = "logging")]
+        solana_program::msg!("🔴 Program {} is not an allowed caller", caller);
+
+        Err(ProgramError::IncorrectProgramId)
+    }
+}
+
+/// Returns the signer seeds as expected by `sol_invoke_signed_c`.
+///
+/// # Arguments
+///
+/// * `seeds`: Seeds used to sign the instruction.
+#[inline(always)]
+pub(crate) fn signer_seeds<const SEEDS: usize>(seeds: &[&[u8]; SEEDS]) -> [CSignerSeed; SEEDS] {
+    std::array::from_fn(|i| CSignerSeed {
+        seed: seeds[i].as_ptr(),
+        len: seeds[i].len() as u64,
+    })
+}
+
+/// Returns the seeds of multiple signers as expected by `sol_invoke_signed_c`.
+///
+/// The seeds of each signer are stored in an array of `MAX_SEEDS` elements, where
+/// only the first elements (up to the number of seeds of the signer) are set.
+///
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seeds of a signer exceed
+/// `MAX_SEEDS`.
+///
+/// # Arguments
+///
+/// * `seeds`: Seeds of each signer of the instruction.
+#[inline(always)]
+pub(crate) fn multi_signer_seeds<const SIGNERS: usize>(
+    seeds: &[&[&[u8]]; SIGNERS],
+) -> Result<[[CSignerSeed; MAX_SEEDS]; SIGNERS], ProgramError> {
+    if seeds.iter().any(|seeds| seeds.len() > MAX_SEEDS) {
+        return Err(ProgramError::MaxSeedLengthExceeded);
+    }
+
+    Ok(std::array::from_fn(|i| bounded_signer_seeds(seeds[i])))
+}
+
+/// Returns the signer seeds of a slice of seeds as expected by `sol_invoke_signed_c`.
+///
+/// The seeds are stored in an array of `MAX_SEEDS` elements, where only the first
+/// elements (up to the number of seeds) are set.
+///
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seeds exceed `MAX_SEEDS`.
+///
+/// # Arguments
+///
+/// * `seeds`: Seeds used to sign the instruction.
+#[inline(always)]
+pub(crate) fn slice_signer_seeds(
+    seeds: &[&[u8]],
+) -> Result<[CSignerSeed; MAX_SEEDS], ProgramError> {
+    if seeds.len() > MAX_SEEDS {
+        return Err(ProgramError::MaxSeedLengthExceeded);
+    }
+
+    Ok(bounded_signer_seeds(seeds))
+}
+
+/// Returns the signer seeds of at most `MAX_SEEDS` seeds.
+#[inline(always)]
+fn bounded_signer_seeds(seeds: &[&[u8]]) -> [CSignerSeed; MAX_SEEDS] {
+    std::array::from_fn(|i| match seeds.get(i) {
+        Some(seed) => CSignerSeed {
+            seed: seed.as_ptr(),
+            len: seed.len() as u64,
+        },
+        None => CSignerSeed {
+            seed: std::ptr::null(),
+            len: 0,
+        },
+    })
+}
+
+/// Invokes a cross-program instruction.
+///
+/// The account infos of the instruction are created on the stack from the accounts,
+/// which must include every account of the instruction.
+///
+/// Returns `ProgramError::AccountBorrowFailed` if a writable account is borrowed or
+/// a read-only account is mutably borrowed.
+///
+/// # Arguments
+///
+/// * `instruction`: Instruction to invoke.
+/// * `accounts`: Accounts required by the instruction.
+#[inline(always)]
+pub fn invoke<const ACCOUNTS: usize>(
+    instruction: &CInstruction,
+    accounts: &[&AccountInfo; ACCOUNTS],
+) -> ProgramResult {
+    invoke_signed::<ACCOUNTS, 0>(instruction, accounts, &[])
+}
+
+/// Invokes a cross-program instruction signed by program derived addresses.
+///
+/// The account infos and signers of the instruction are created on the stack, so
+/// the instruction can be signed by multiple program derived addresses.
+///
+/// Returns `ProgramError::AccountBorrowFailed` if a writable account is borrowed or
+/// a read-only account is mutably borrowed, or `ProgramError::MaxSeedLengthExceeded`
+/// if the seeds of a signer exceed `MAX_SEEDS`.
+///
+/// # Arguments
+///
+/// * `instruction`: Instruction to invoke.
+/// * `accounts`: Accounts required by the instruction.
+/// * `signers_seeds`: Seeds of each signer of the instruction.
+#[inline(always)]
+pub fn invoke_signed<const ACCOUNTS: usize, const SIGNERS: usize>(
+    instruction: &CInstruction,
+    accounts: &[&AccountInfo; ACCOUNTS],
+    signers_seeds: &[&[&[u8]]; SIGNERS],
+) -> ProgramResult {
+    let seeds = multi_signer_seeds(signers_seeds)?;
+    let signers: [CSigner; SIGNERS] = std::array::from_fn(|i| CSigner {
+        seeds: seeds[i].as_ptr(),
+        len: signers_seeds[i].len() as u64,
+    });
+
+    let account_infos: [CAccountInfo; ACCOUNTS] =
+        std::array::from_fn(|i| CAccountInfo::from(accounts[i]));
+
+    invoke_signed_c(instruction, &account_infos, &signers)
+}
+
+/// Invokes a cross-program instruction signed by program derived addresses without
+/// checking the borrow state of the accounts.
+///
+/// This avoids the overhead of the borrow checks when the program has verified that
+/// the accounts are not duplicated and not borrowed, analogous to the
+/// `unchecked_borrow_*` accessors of `AccountInfo`.
+///
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seeds of a signer exceed
+/// `MAX_SEEDS`.
+///
+/// # Arguments
+///
+/// * `instruction`: Instruction to invoke.
+/// * `accounts`: Accounts required by the instruction.
+/// * `signers_seeds`: Seeds of each signer of the instruction.
+///
+/// # Safety
+///
+/// The caller must guarantee that there are no active borrows of the lamports or
+/// data of any account, since they can be modified by the invoked program.
+#[inline(always)]
+pub unsafe fn invoke_signed_unchecked<const ACCOUNTS: usize, const SIGNERS: usize>(
+    instruction: &CInstruction,
+    accounts: &[&AccountInfo; ACCOUNTS],
+    signers_seeds: &[&[&[u8]]; SIGNERS],
+) -> ProgramResult {
+    let seeds = multi_signer_seeds(signers_seeds)?;
+    let signers: [CSigner; SIGNERS] = std::array::from_fn(|i| CSigner {
+        seeds: seeds[i].as_ptr(),
+        len: signers_seeds[i].len() as u64,
+    });
+
+    let account_infos: [CAccountInfo; ACCOUNTS] =
+        std::array::from_fn(|i| CAccountInfo::from(accounts[i]));
+
+    invoke_signed_c_unchecked(instruction, &account_infos, &signers)
+}
+
+/// Checks that the lamports and data of an account can be modified by an invoked
+/// program.
+///
+/// Returns `ProgramError::AccountBorrowFailed` if a writable account is borrowed or
+/// a read-only account is mutably borrowed.
+#[inline(always)]
+fn check_borrow_state(account_info: &CAccountInfo) -> ProgramResult {
+    // the account infos of the crate are created from `AccountInfo` references, so
+    // the key is at a fixed offset from the borrow state of the account
+    let borrow_state = unsafe { *(account_info.key as *const u8).sub(KEY_OFFSET) };
+
+    // writable accounts can be modified by the invoked program, so any borrow
+    // conflicts with the invocation
+    let conflicts = if account_info.is_writable {
+        borrow_state != 0
+    } else {
+        borrow_state & 0b_1000_1000 != 0
+    };
+
+    if conflicts {
+        Err(ProgramError::AccountBorrowFailed)
+    } else {
+        Ok(())
+    }
+}
+
+/// Invokes a cross-program instruction forwarding all the accounts of the current
+/// instruction.
+///
+/// The account infos are created on the stack with capacity for `MAX_ACCOUNTS`
+/// accounts, so the capacity should be kept small enough for the stack frame.
+///
+/// Returns `ProgramError::AccountBorrowFailed` if a writable account is borrowed or
+/// a read-only account is mutably borrowed.
+///
+/// Returns `ProgramError::InvalidArgument` if there are more than `MAX_ACCOUNTS`
+/// accounts.
+///
+/// # Arguments
+///
+/// * `instruction`: Instruction to invoke.
+/// * `accounts`: Accounts of the current instruction.
+#[inline(always)]
+pub fn invoke_with_all_accounts<const MAX_ACCOUNTS: usize>(
+    instruction: &CInstruction,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    invoke_signed_with_all_accounts::<MAX_ACCOUNTS, 0>(instruction, accounts, &[])
+}
+
+/// Invokes a cross-program instruction signed by program derived addresses
+/// forwarding all the accounts of the current instruction.
+///
+/// The account infos are created on the stack with capacity for `MAX_ACCOUNTS`
+/// accounts, so the capacity should be kept small enough for the stack frame.
+///
+/// Returns `ProgramError::AccountBorrowFailed` if a writable account is borrowed or
+/// a read-only account is mutably borrowed.
+///
+/// Returns `ProgramError::InvalidArgument` if there are more than `MAX_ACCOUNTS`
+/// accounts or `ProgramError::MaxSeedLengthExceeded` if the seeds of a signer exceed
+/// `MAX_SEEDS`.
+///
+/// # Arguments
+///
+/// * `instruction`: Instruction to invoke.
+/// * `accounts`: Accounts of the current instruction.
+/// * `signers_seeds`: Seeds of each signer of the instruction.
+#[inline(always)]
+pub fn invoke_signed_with_all_accounts<const MAX_ACCOUNTS: usize, const SIGNERS: usize>(
+    instruction: &CInstruction,
+    accounts: &[AccountInfo],
+    signers_seeds: &[&[&[u8]]; SIGNERS],
+) -> ProgramResult {
+    if accounts.len() > MAX_ACCOUNTS {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let seeds = multi_signer_seeds(signers_seeds)?;
+    let signers: [CSigner; SIGNERS] = std::array::from_fn(|i| CSigner {
+        seeds: seeds[i].as_ptr(),
+        len: signers_seeds[i].len() as u64,
+    });
+
+    let mut account_infos: [MaybeUninit<CAccountInfo>; MAX_ACCOUNTS] =
+        unsafe { MaybeUninit::uninit().assume_init() };
+
+    accounts
+        .iter()
+        .zip(account_infos.iter_mut())
+        .for_each(|(account, info)| {
+            info.write(CAccountInfo::from(account));
+        });
+
+    // only the first `accounts.len()` account infos are initialized
+    let account_infos = unsafe {
+        std::slice::from_raw_parts(
+            account_infos.as_ptr() as *const CAccountInfo,
+            accounts.len(),
+        )
+    };
+
+    invoke_signed_c(instruction, account_infos, &signers)
+}
+
+/// Invokes a cross-program instruction using a subset of the accounts of the
+/// current instruction.
+///
+/// The account metas and infos of the instruction are created in the order of the
+/// indices, using the writable and signer flags of the accounts.
+///
+/// Returns `ProgramError::AccountBorrowFailed` if a writable account is borrowed or
+/// a read-only account is mutably borrowed.
+///
+/// Returns `ProgramError::NotEnoughAccountKeys` if an index is out of bounds.
+///
+/// # Arguments
+///
+/// * `program_id`: Program to invoke.
+/// * `accounts`: Accounts of the current instruction.
+/// * `indices`: Indices of the accounts required by the instruction.
+/// * `data`: Data of the instruction.
+#[inline(always)]
+pub fn invoke_indexed<const ACCOUNTS: usize>(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    indices: &[usize; ACCOUNTS],
+    data: &[u8],
+) -> ProgramResult {
+    if indices.iter().any(|index| *index >= accounts.len()) {
+        return Err(ProgramError::NotEnoughAccountKeys);
+    }
+
+    let instruction_accounts: [CAccountMeta; ACCOUNTS] =
+        std::array::from_fn(|i| CAccountMeta::from(&accounts[indices[i]]));
+
+    let instruction = CInstruction {
+        program_id,
+        accounts: instruction_accounts.as_ptr(),
+        accounts_len: ACCOUNTS as u64,
+        data: data.as_ptr(),
+        data_len: data.len() as u64,
+    };
+
+    let account_infos: [CAccountInfo; ACCOUNTS] =
+        std::array::from_fn(|i| CAccountInfo::from(&accounts[indices[i]]));
+
+    invoke_signed_c(&instruction, &account_infos, &[])
+}
+
+/// Invokes a cross-program instruction signed by a program derived address with a
+/// slice of seeds.
+///
+/// This is useful when the number of seeds is only known at runtime (e.g., an
+/// optional bump seed); the seeds are stored in a `MAX_SEEDS` stack buffer.
+///
+/// Returns `ProgramError::AccountBorrowFailed` if a writable account is borrowed or
+/// a read-only account is mutably borrowed.
+///
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seeds exceed `MAX_SEEDS`.
+///
+/// # Arguments
+///
+/// * `instruction`: Instruction to invoke.
+/// * `accounts`: Accounts required by the instruction.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+#[inline(always)]
+pub fn invoke_signed_slice<const ACCOUNTS: usize>(
+    instruction: &CInstruction,
+    accounts: &[&AccountInfo; ACCOUNTS],
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let seeds = slice_signer_seeds(signer_seeds)?;
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: signer_seeds.len() as u64,
+    }];
+
+    let account_infos: [CAccountInfo; ACCOUNTS] =
+        std::array::from_fn(|i| CAccountInfo::from(accounts[i]));
+
+    invoke_signed_c(instruction, &account_infos, &signer)
+}
+
+/// Checks that an instruction is within the limits of a cross-program invocation.
+///
+/// The runtime rejects invocations exceeding the limits with an opaque error, so
+/// checking them beforehand reports which limit was exceeded.
+///
+/// Returns `ProgramError::InvalidArgument` if there are more than
+/// `MAX_CPI_ACCOUNT_INFOS` account infos, more than `MAX_CPI_INSTRUCTION_ACCOUNTS`
+/// accounts or more than `MAX_CPI_INSTRUCTION_DATA_LEN` bytes of data.
+///
+/// # Arguments
+///
+/// * `instruction`: Instruction to invoke.
+/// * `account_infos_len`: Number of account infos of the invocation.
+#[inline(always)]
+pub fn check_cpi_limits(instruction: &CInstruction, account_infos_len: usize) -> ProgramResult {
+    if account_infos_len > MAX_CPI_ACCOUNT_INFOS {
+        #[cfg(feature = "logging")]
+        solana_program::msg!(
+            "🔴 CPI with {} account infos exceeds the maximum of {}",
+            account_infos_len,
+            MAX_CPI_ACCOUNT_INFOS
+        );
+
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if instruction.accounts_len > MAX_CPI_INSTRUCTION_ACCOUNTS as u64 {
+        #[cfg(feature = "logging")]
+        solana_program::msg!(
+            "🔴 CPI with {} accounts exceeds the maximum of {}",
+            instruction.accounts_len,
+            MAX_CPI_INSTRUCTION_ACCOUNTS
+        );
+
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    if instruction.data_len > MAX_CPI_INSTRUCTION_DATA_LEN {
+        #[cfg(feature = "logging")]
+        solana_program::msg!(
+            "🔴 CPI with {} bytes of data exceeds the maximum of {}",
+            instruction.data_len,
+            MAX_CPI_INSTRUCTION_DATA_LEN
+        );
+
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    Ok(())
+}
+
+/// Invokes a cross-program instruction.
+///
+/// Every safe CPI helper of the crate goes through this function, so the borrow
+/// state of the accounts is always checked; the `unsafe` unchecked variants are the
+/// only way to skip the check.
+///
+/// Returns `ProgramError::AccountBorrowFailed` if a writable account is borrowed or
+/// a read-only account is mutably borrowed, or `ProgramError::InvalidArgument` if
+/// the instruction exceeds the limits of a cross-program invocation.
+///
+/// # Arguments
+///
+/// * `instruction`: Instruction to invoke.
+/// * `account_infos`: Accounts required by the instruction.
+/// * `signers`: Seeds used to sign the instruction.
+#[inline(always)]
+pub(crate) fn invoke_signed_c(
+    instruction: &CInstruction,
+    account_infos: &[CAccountInfo],
+    signers: &[CSigner],
+) -> ProgramResult {
+    account_infos.iter().try_for_each(check_borrow_state)?;
+
+    // SAFETY: there are no active borrows that conflict with the invoked program
+    unsafe { invoke_signed_c_unchecked(instruction, account_infos, signers) }
+}
+
+/// Invokes a cross-program instruction without checking the borrow state of the
+/// accounts.
+///
+/// Returns `ProgramError::InvalidArgument` if the instruction exceeds the limits of
+/// a cross-program invocation.
+///
+/// # Arguments
+///
+/// * `instruction`: Instruction to invoke.
+/// * `account_infos`: Accounts required by the instruction.
+/// * `signers`: Seeds used to sign the instruction.
+///
+/// # Safety
+///
+/// The caller must guarantee that there are no active borrows of the lamports or
+/// data of any account, since they can be modified by the invoked program.
+#[inline(always)]
+pub(crate) unsafe fn invoke_signed_c_unchecked(
+    instruction: &CInstruction,
+    account_infos: &[CAccountInfo],
+    signers: &[CSigner],
+) -> ProgramResult {
+    check_cpi_limits(instruction, account_infos.len())?;
+
+    // the account infos are created from `AccountInfo` references by the helpers
+    // of the crate
+    unsafe { invoke_unchecked(instruction, account_infos, signers) }
+}
+
+/// Invokes a cross-program instruction without any validation.
+///
+/// The instruction, account infos and signers are passed directly to
+/// `sol_invoke_signed_c`, which avoids the overhead of the CPI helpers when invoking
+/// instructions in a hot loop. The helpers of the `system` and `token` modules should
+/// be preferred in general.
+///
+/// # Arguments
+///
+/// * `instruction`: Instruction to invoke.
+/// * `account_infos`: Accounts required by the instruction.
+/// * `signers`: Seeds used to sign the instruction.
+///
+/// # Safety
+///
+/// The caller must guarantee that:
+///
+/// * the account infos were created from accounts received by the program (e.g.,
+///   using `CAccountInfo::from`) and every account of the instruction is included;
+/// * there are no active borrows of the lamports or data of any account, since
+///   they can be modified by the invoked program;
+/// * the pointers of the instruction and signers are valid for the duration of
+///   the call.
+#[inline(always)]
+pub unsafe fn invoke_unchecked(
+    instruction: &CInstruction,
+    account_infos: &[CAccountInfo],
+    signers: &[CSigner],
+) -> ProgramResult {
+    #[cfg(target_os = "solana")]
+    let result = solana_program::syscalls::sol_invoke_signed_c(
+        instruction as *const CInstruction as *const u8,
+        account_infos.as_ptr() as *const u8,
+        account_infos.len() as u64,
+        signers.as_ptr() as *const u8,
+        signers.len() as u64,
+    );
+
+    // keep clippy happy
+    #[cfg(not(target_os = "solana"))]
+    let result = {
+        core::hint::black_box(&(&instruction, &account_infos, &signers));
+        SUCCESS
+    };
+
+    match result {
+        SUCCESS => Ok(()),
+        error => Err(error.into()),
+    }
+}
+
+/// Sets the return data of the current instruction.
+///
+/// The return data can be read by the caller of the program after the CPI returns.
+///
+/// # Arguments
+///
+/// * `data`: Return data (at most `MAX_RETURN_DATA` bytes).
+#[inline(always)]
+pub fn set_return_data(data: &[u8]) {
+    #[cfg(target_os = "solana")]
+    unsafe {
+        solana_program::syscalls::sol_set_return_data(data.as_ptr(), data.len() as u64)
+    };
+
+    // keep clippy happy
+    #[cfg(not(target_os = "solana"))]
+    core::hint::black_box(data);
+}
+
+/// Returns the return data of the last invoked instruction and the program that
+/// set it.
+///
+/// Returns `None` if there is no return data.
+#[inline(always)]
+pub fn get_return_data() -> Option<(Pubkey, Vec<u8>)> {
+    let mut buffer = [0u8; MAX_RETURN_DATA];
+
+    get_return_data_into(&mut buffer).map(|(program_id, length)| {
+        (
+            program_id,
+            buffer[..std::cmp::min(length, MAX_RETURN_DATA)].to_vec(),
+        )
+    })
+}
+
+/// Writes the return data of the last invoked instruction into a buffer, returning
+/// the program that set it and the length of the return data.
+///
+/// The return data is truncated when it is longer than the buffer, in which case
+/// the returned length is larger than the length of the buffer.
+///
+/// Returns `None` if there is no return data.
+///
+/// # Arguments
+///
+/// * `buffer`: Buffer to write the return data to.
+#[inline(always)]
+pub fn get_return_data_into(buffer: &mut [u8]) -> Option<(Pubkey, usize)> {
+    let mut program_id = Pubkey::default();
+
+    #[cfg(target_os = "solana")]
+    let length = unsafe {
+        solana_program::syscalls::sol_get_return_data(
+            buffer.as_mut_ptr(),
+            buffer.len() as u64,
+            &mut program_id,
+        )
+    } as usize;
+
+    // keep clippy happy
+    #[cfg(not(target_os = "solana"))]
+    let length = {
+        core::hint::black_box(&(&buffer, &mut program_id));
+        0
+    };
+
+    if length == 0 {
+        None
+    } else {
+        Some((program_id, length))
+    }
+}
+
+/// Builder of a cross-program instruction.
+///
+/// The account metas, account infos and data of the instruction are stored on the
+/// stack, with capacity for `ACCOUNTS` accounts and `DATA` bytes of data.
+///
+/// # Examples
+///
+/// ```ignore
+/// // transfer lamports using the System program
+/// CpiBuilder::<2, 12>::new(&system_program::ID)
+///     .signer(from)
+///     .account(recipient)
+///     .data(&2u32.to_le_bytes())
+///     .data(&amount.to_le_bytes())
+///     .invoke()?;
+/// ```
+pub struct CpiBuilder<'a, const ACCOUNTS: usize, const DATA: usize> {
+    /// Program to invoke.
+    program_id: &'a Pubkey,
+
+    /// Account metas of the instruction.
+    metas: [MaybeUninit<CAccountMeta>; ACCOUNTS],
+
+    /// Account infos of the instruction.
+    infos: [MaybeUninit<CAccountInfo>; ACCOUNTS],
+
+    /// Number of accounts of the instruction.
+    accounts_len: usize,
+
+    /// Data of the instruction.
+    data: [u8; DATA],
+
+    /// Length of the data of the instruction.
+    data_len: usize,
+
+    /// Indicates whether the capacity of the builder was exceeded.
+    overflow: bool,
+
+    /// The builder holds pointers to the accounts.
+    _accounts: std::marker::PhantomData<&'a AccountInfo>,
+}
+
+impl<'a, const ACCOUNTS: usize, const DATA: usize> CpiBuilder<'a, ACCOUNTS, DATA> {
+    /// Creates a builder for an instruction of the program.
+    ///
+    /// # Arguments
+    ///
+    /// * `program_id`: Program to invoke.
+    #[inline(always)]
+    pub fn new(program_id: &'a Pubkey) -> Self {
+        Self {
+            program_id,
+            metas: unsafe { MaybeUninit::uninit().assume_init() },
+            infos: unsafe { MaybeUninit::uninit().assume_init() },
+            accounts_len: 0,
+            data: [0; DATA],
+            data_len: 0,
+            overflow: false,
+            _accounts: std::marker::PhantomData,
+        }
+    }
+
+    /// Adds an account to the instruction, using the writable and signer flags of
+    /// the account.
+    ///
+    /// # Arguments
+    ///
+    /// * `account`: Account required by the instruction.
+    #[inline(always)]
+    pub fn account(self, account: &'a AccountInfo) -> Self {
+        self.push_account(account, account.to_account_meta())
+    }
+
+    /// Adds an account that signs the instruction (e.g., a program derived address
+    /// signing with seeds).
+    ///
+    /// # Arguments
+    ///
+    /// * `account`: Account required by the instruction.
+    #[inline(always)]
+    pub fn signer(self, account: &'a AccountInfo) -> Self {
+        self.push_account(account, AsSigner(account).to_account_meta())
+    }
+
+    /// Appends bytes to the data of the instruction.
+    ///
+    /// # Arguments
+    ///
+    /// * `data`: Bytes to append.
+    #[inline(always)]
+    pub fn data(mut self, data: &[u8]) -> Self {
+        if self.data_len + data.len() > DATA {
+            self.overflow = true;
+        } else {
+            self.data[self.data_len..self.data_len + data.len()].copy_from_slice(data);
+            self.data_len += data.len();
+        }
+
+        self
+    }
+
+    /// Invokes the instruction.
+    ///
+    /// Returns `ProgramError::AccountBorrowFailed` if a writable account is borrowed or
+    /// a read-only account is mutably borrowed.
+    ///
+    /// Returns `ProgramError::InvalidArgument` if the capacity of the builder was
+    /// exceeded.
+    #[inline(always)]
+    pub fn invoke(&self) -> ProgramResult {
+        self.invoke_with_signers(&[])
+    }
+
+    /// Invokes the instruction signed by program derived addresses.
+    ///
+    /// Returns `ProgramError::AccountBorrowFailed` if a writable account is borrowed or
+    /// a read-only account is mutably borrowed.
+    ///
+    /// Returns `ProgramError::InvalidArgument` if the capacity of the builder was
+    /// exceeded or `ProgramError::MaxSeedLengthExceeded` if the seeds of a signer
+    /// exceed `MAX_SEEDS`.
+    ///
+    /// # Arguments
+    ///
+    /// * `signers_seeds`: Seeds of each signer of the instruction.
+    #[inline(always)]
+    pub fn invoke_signed<const SIGNERS: usize>(
+        &self,
+        signers_seeds: &[&[&[u8]]; SIGNERS],
+    ) -> ProgramResult {
+        let seeds = multi_signer_seeds(signers_seeds)?;
+        let signers: [CSigner; SIGNERS] = std::array::from_fn(|i| CSigner {
+            seeds: seeds[i].as_ptr(),
+            len: signers_seeds[i].len() as u64,
+        });
+
+        self.invoke_with_signers(&signers)
+    }
+
+    #[inline(always)]
+    fn push_account(mut self, account: &'a AccountInfo, meta: CAccountMeta) -> Self {
+        if self.accounts_len == ACCOUNTS {
+            self.overflow = true;
+        } else {
+            self.metas[self.accounts_len].write(meta);
+            self.infos[self.accounts_len].write(CAccountInfo::from(account));
+            self.accounts_len += 1;
+        }
+
+        self
+    }
+
+    #[inline(always)]
+    fn invoke_with_signers(&self, signers: &[CSigner]) -> ProgramResult {
+        if self.overflow {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        let instruction = CInstruction {
+            program_id: self.program_id,
+            accounts: self.metas.as_ptr() as *const CAccountMeta,
+            accounts_len: self.accounts_len as u64,
+            data: self.data.as_ptr(),
+            data_len: self.data_len as u64,
+        };
+
+        // only the first `accounts_len` account infos are initialized
+        let account_infos = unsafe {
+            std::slice::from_raw_parts(
+                self.infos.as_ptr() as *const CAccountInfo,
+                self.accounts_len,
+            )
+        };
+
+        invoke_signed_c(&instruction, account_infos, signers)
+    }
+}
+
+/// Returns the return data of the last invoked instruction as a value of type `T`.
+///
+/// The return data must be set by the expected program and contain at least the
+/// bytes of `T`; any trailing bytes are ignored.
+///
+/// Returns `ProgramError::IncorrectProgramId` if the return data was set by another
+/// program or `ProgramError::InvalidArgument` if there is no return data or it is
+/// shorter than `T`.
+///
+/// # Arguments
+///
+/// * `program_id`: Program expected to set the return data.
+#[inline(always)]
+pub fn get_return_data_as<T: Pod>(program_id: &Pubkey) -> Result<T, ProgramError> {
+    let (value, length) = read_return_data::<T>(program_id)?;
+
+    if length < std::mem::size_of::<T>() {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    Ok(value)
+}
+
+/// Returns the return data of the last invoked instruction as a value of type `T`,
+/// requiring the return data to have exactly the size of `T`.
+///
+/// Returns `ProgramError::IncorrectProgramId` if the return data was set by another
+/// program or `ProgramError::InvalidArgument` if there is no return data or its
+/// length is different than the size of `T`.
+///
+/// # Arguments
+///
+/// * `program_id`: Program expected to set the return data.
+#[inline(always)]
+pub fn get_return_data_exact<T: Pod>(program_id: &Pubkey) -> Result<T, ProgramError> {
+    let (value, length) = read_return_data::<T>(program_id)?;
+
+    if length != std::mem::size_of::<T>() {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    Ok(value)
+}
+
+/// Reads the return data into a value of type `T`, returning the value and the
+/// length of the return data.
+#[inline(always)]
+fn read_return_data<T: Pod>(program_id: &Pubkey) -> Result<(T, usize), ProgramError> {
+    let mut value = T::zeroed();
+
+    let (caller, length) = get_return_data_into(bytemuck::bytes_of_mut(&mut value))
+        .ok_or(ProgramError::InvalidArgument)?;
+
+    if &caller != program_id {
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    Ok((value, length))
+}
+
+/// Zero-copy view over a processed sibling instruction.
+///
+/// The data and account metas of the instruction are stored in buffers provided by
+/// the caller of [`get_processed_sibling_instruction`].
+pub struct SiblingInstruction<'a> {
+    /// Program id of the instruction.
+    program_id: Pubkey,
+
+    /// Account metas of the instruction.
+    accounts: &'a [AccountMeta],
+
+    /// Data of the instruction.
+    data: &'a [u8],
+}
+
+impl<'a> SiblingInstruction<'a> {
+    /// Returns the program id of the instruction.
+    #[inline(always)]
+    pub fn program_id(&self) -> &Pubkey {
+        &self.program_id
+    }
+
+    /// Returns the account metas of the instruction.
+    #[inline(always)]
+    pub fn accounts(&self) -> &'a [AccountMeta] {
+        self.accounts
+    }
+
+    /// Returns the data of the instruction.
+    #[inline(always)]
+    pub fn data(&self) -> &'a [u8] {
+        self.data
+    }
+}
+
+/// Returns a processed sibling instruction of the current instruction.
+///
+/// Sibling instructions are instructions invoked at the same stack height as the
+/// current instruction, where the index `0` is the most recently processed one. The
+/// data and account metas of the instruction are written to the buffers, so the
+/// instruction can be read without allocating.
+///
+/// Returns `None` if there is no sibling instruction at the index or
+/// `ProgramError::InvalidArgument` if the buffers are too small for the instruction.
+///
+/// # Arguments
+///
+/// * `index`: Index of the sibling instruction (in reverse order of processing).
+/// * `data`: Buffer for the data of the instruction.
+/// * `accounts`: Buffer for the account metas of the instruction.
+pub fn get_processed_sibling_instruction<'a>(
+    index: usize,
+    data: &'a mut [u8],
+    accounts: &'a mut [AccountMeta],
+) -> Result<Option<SiblingInstruction<'a>>, ProgramError> {
+    let mut meta = ProcessedSiblingInstruction::default();
+    let mut program_id = Pubkey::default();
+
+    // the first call only returns the lengths of the instruction
+    #[cfg(target_os = "solana")]
+    let found = unsafe {
+        solana_program::syscalls::sol_get_processed_sibling_instruction(
+            index as u64,
+            &mut meta,
+            &mut program_id,
+            data.as_mut_ptr(),
+            accounts.as_mut_ptr(),
+        )
+    } == 1;
+
+    // keep clippy happy
+    #[cfg(not(target_os = "solana"))]
+    let found = {
+        core::hint::black_box(&(index, &mut meta, &mut program_id));
+        false
+    };
+
+    if !found {
+        return Ok(None);
+    }
+
+    let data_len = meta.data_len as usize;
+    let accounts_len = meta.accounts_len as usize;
+
+    if data.len() < data_len || accounts.len() < accounts_len {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // the instruction is only copied when the lengths match
+    #[cfg(target_os = "solana")]
+    unsafe {
+        solana_program::syscalls::sol_get_processed_sibling_instruction(
+            index as u64,
+            &mut meta,
+            &mut program_id,
+            data.as_mut_ptr(),
+            accounts.as_mut_ptr(),
+        )
+    };
+
+    Ok(Some(SiblingInstruction {
+        program_id,
+        accounts: &accounts[..accounts_len],
+        data: &data[..data_len],
+    }))
+}
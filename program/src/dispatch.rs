@@ -0,0 +1,86 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Helpers to dispatch instructions by discriminator.
+//!
+//! The first byte of the instruction data is the discriminator of the instruction,
+//! which selects the handler that processes it. Instructions with an unknown
+//! discriminator can be routed to a fallback handler instead of failing, which
+//! proxy and upgradeable-interface programs use to forward them.
+
+use solana_program::{entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::AccountInfo;
+
+/// Function to process an instruction.
+pub type Handler = fn(&Pubkey, &[AccountInfo], &[u8]) -> ProgramResult;
+
+/// Processes the instruction with the handler of its discriminator.
+///
+/// The handler at the index of the discriminator receives the instruction data
+/// following the discriminator. When the instruction data is empty or there is no
+/// handler for the discriminator, the `fallback` handler receives the complete
+/// instruction data; `ProgramError::InvalidInstructionData` is returned if no
+/// fallback is specified.
+///
+/// # Arguments
+///
+/// * `program_id`: Program id of the program.
+/// * `accounts`: Accounts of the instruction.
+/// * `instruction_data`: Instruction data (discriminator followed by the payload).
+/// * `handlers`: Handlers indexed by their discriminator.
+/// * `fallback`: Handler of instructions with an unknown discriminator.
+///
+/// # Examples
+///
+/// ```ignore
+/// entrypoint!(process_instruction, 10);
+///
+/// pub fn process_instruction(
+///     program_id: &Pubkey,
+///     accounts: &[AccountInfo],
+///     instruction_data: &[u8],
+/// ) -> ProgramResult {
+///     dispatch::process(
+///         program_id,
+///         accounts,
+///         instruction_data,
+///         &[process_create, process_update],
+///         Some(process_fallback),
+///     )
+/// }
+/// ```
+#[inline(always)]
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+    handlers: &[Handler],
+    fallback: Option<Handler>,
+) -> ProgramResult {
+    let handler = instruction_data
+        .split_first()
+        .and_then(|(discriminator, data)| Some((handlers.get(*discriminator as usize)?, data)));
+
+    match (handler, fallback) {
+        (Some((handler, data)), _) => handler(program_id, accounts, data),
+        (None, Some(fallback)) => fallback(program_id, accounts, instruction_data),
+        (None, None) => {
+            #[cfg(feature = "logging")]
+            solana_program::msg!("🔴 Unknown instruction discriminator");
+
+            Err(ProgramError::InvalidInstructionData)
+        }
+    }
+}
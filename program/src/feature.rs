@@ -0,0 +1,63 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Runtime feature gate helpers.
+
+use solana_program::{clock::Slot, feature, program_error::ProgramError};
+
+use crate::AccountInfo;
+
+/// Size of the feature account data.
+///
+/// The data is the `bincode` serialization of an `Option<Slot>`: a 1-byte
+/// tag followed by the activation slot.
+pub const FEATURE_LEN: usize = 9;
+
+/// Returns the slot at which the feature was activated.
+///
+/// A feature account that exists but has not been activated by the runtime
+/// yet returns `None`.
+///
+/// # Arguments
+///
+/// * `feature`: Feature account.
+pub fn activated_at(feature: &AccountInfo) -> Result<Option<Slot>, ProgramError> {
+    if feature.owner() != &feature::ID {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let data = feature.try_borrow_data()?;
+
+    if data.len() < FEATURE_LEN {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    // -    0: option tag
+    // - 1..9: activation slot
+    match data[0] {
+        0 => Ok(None),
+        1 => Ok(Some(u64::from_le_bytes(data[1..9].try_into().unwrap()))),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+/// Indicates whether the feature is active.
+///
+/// # Arguments
+///
+/// * `feature`: Feature account.
+#[inline(always)]
+pub fn is_active(feature: &AccountInfo) -> Result<bool, ProgramError> {
+    activated_at(feature).map(|slot| slot.is_some())
+}
@@ -0,0 +1,216 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Introspection, reset and scratch arenas of the program heap.
+//!
+//! These helpers operate on the state of the bump allocator set up by the
+//! `entrypoint!` macro, which allocates downwards from the end of the heap and
+//! stores the current position in the first bytes of the heap. They must not be
+//! used with a custom global allocator. Off-chain, the heap is not tracked: the
+//! whole heap is reported as available and resets have no effect.
+
+use std::{alloc::Layout, cell::Cell, ptr::NonNull};
+
+use solana_program::entrypoint::HEAP_LENGTH;
+#[cfg(target_os = "solana")]
+use solana_program::entrypoint::HEAP_START_ADDRESS;
+
+/// Position of the bump allocator.
+///
+/// A checkpoint can be used to reset the heap, freeing every allocation made after
+/// the checkpoint was taken.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Checkpoint(usize);
+
+/// Returns the number of bytes allocated on the heap.
+#[inline(always)]
+pub fn used() -> usize {
+    HEAP_LENGTH - std::mem::size_of::<usize>() - remaining()
+}
+
+/// Returns the number of bytes available on the heap.
+///
+/// Allocations might use less than the available bytes due to alignment.
+#[inline(always)]
+pub fn remaining() -> usize {
+    #[cfg(target_os = "solana")]
+    {
+        position() - (HEAP_START_ADDRESS as usize + std::mem::size_of::<usize>())
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        HEAP_LENGTH - std::mem::size_of::<usize>()
+    }
+}
+
+/// Returns a checkpoint of the current position of the heap.
+#[inline(always)]
+pub fn checkpoint() -> Checkpoint {
+    #[cfg(target_os = "solana")]
+    {
+        Checkpoint(position())
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    {
+        Checkpoint(HEAP_LENGTH)
+    }
+}
+
+/// Resets the heap to a checkpoint, freeing every allocation made after it.
+///
+/// # Safety
+///
+/// The caller must guarantee that none of the values allocated after the checkpoint
+/// are used after the reset, since their memory will be reused by subsequent
+/// allocations.
+#[inline(always)]
+pub unsafe fn reset(checkpoint: Checkpoint) {
+    #[cfg(target_os = "solana")]
+    {
+        *(HEAP_START_ADDRESS as *mut usize) = checkpoint.0;
+    }
+
+    #[cfg(not(target_os = "solana"))]
+    core::hint::black_box(checkpoint);
+}
+
+/// Runs a function and resets the heap afterwards, so the heap space used by the
+/// function can be reused by the next phases of the instruction.
+///
+/// # Safety
+///
+/// The caller must guarantee that no value allocated by the function (e.g., in its
+/// return value) is used after it returns.
+#[inline(always)]
+pub unsafe fn scoped<R>(f: impl FnOnce() -> R) -> R {
+    let checkpoint = checkpoint();
+    let result = f();
+    reset(checkpoint);
+
+    result
+}
+
+/// Arena of scratch memory on the heap.
+///
+/// The arena allocates its buffer once and hands out slices of it, which are all
+/// freed when the arena is dropped. This provides cheap temporary buffers (e.g., to
+/// build instruction data or hash inputs) without wasting heap space, since the bump
+/// allocator never frees memory. The heap is only reset when nothing else was
+/// allocated after the arena; otherwise its buffer is simply not reused.
+pub struct Scratch {
+    /// Start of the buffer.
+    buffer: NonNull<u8>,
+
+    /// Size of the buffer.
+    capacity: usize,
+
+    /// Number of bytes handed out.
+    used: Cell<usize>,
+
+    /// Heap position before the buffer was allocated.
+    #[cfg(target_os = "solana")]
+    checkpoint: Checkpoint,
+}
+
+impl Scratch {
+    /// Alignment of the buffer.
+    const ALIGN: usize = 8;
+
+    /// Creates a new arena with a (zeroed) buffer of `capacity` bytes.
+    ///
+    /// Returns `None` if the heap does not have enough space for the buffer.
+    pub fn with_capacity(capacity: usize) -> Option<Self> {
+        #[cfg(target_os = "solana")]
+        let checkpoint = checkpoint();
+
+        let buffer = if capacity == 0 {
+            NonNull::dangling()
+        } else {
+            let layout = Layout::from_size_align(capacity, Self::ALIGN).ok()?;
+            NonNull::new(unsafe { std::alloc::alloc_zeroed(layout) })?
+        };
+
+        Some(Self {
+            buffer,
+            capacity,
+            used: Cell::new(0),
+            #[cfg(target_os = "solana")]
+            checkpoint,
+        })
+    }
+
+    /// Returns a slice of `len` bytes of the arena.
+    ///
+    /// The slice is zeroed, since the memory of the arena is never reused while the
+    /// arena is alive. Returns `None` if the arena does not have enough space left.
+    // each slice covers a distinct region of the buffer, so multiple mutable slices
+    // can be handed out from a shared reference
+    #[allow(clippy::mut_from_ref)]
+    pub fn alloc(&self, len: usize) -> Option<&mut [u8]> {
+        let offset = self.used.get();
+        let end = offset
+            .checked_add(len)
+            .filter(|end| *end <= self.capacity)?;
+        self.used.set(end);
+
+        Some(unsafe { std::slice::from_raw_parts_mut(self.buffer.as_ptr().add(offset), len) })
+    }
+
+    /// Returns the size of the arena.
+    #[inline(always)]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of bytes still available in the arena.
+    #[inline(always)]
+    pub fn remaining(&self) -> usize {
+        self.capacity - self.used.get()
+    }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        // the buffer can only be freed when it is the last allocation of the heap
+        #[cfg(target_os = "solana")]
+        if position() == self.buffer.as_ptr() as usize {
+            unsafe { reset(self.checkpoint) };
+        }
+
+        #[cfg(not(target_os = "solana"))]
+        unsafe {
+            std::alloc::dealloc(
+                self.buffer.as_ptr(),
+                Layout::from_size_align_unchecked(self.capacity, Self::ALIGN),
+            );
+        }
+    }
+}
+
+/// Returns the current position of the bump allocator.
+#[cfg(target_os = "solana")]
+#[inline(always)]
+pub(crate) fn position() -> usize {
+    // the position is only initialized on the first allocation
+    match unsafe { *(HEAP_START_ADDRESS as *const usize) } {
+        0 => HEAP_START_ADDRESS as usize + HEAP_LENGTH,
+        position => position,
+    }
+}
@@ -0,0 +1,245 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Random-access parser of a serialized input buffer.
+//!
+//! The parser reads the input as a byte slice, without modifying it or relying on
+//! its alignment, so it can be used off-chain (e.g., by explorers, simulators and
+//! debuggers) to inspect individual accounts of an instruction.
+
+use solana_program::{
+    entrypoint::{BPF_ALIGN_OF_U128, MAX_PERMITTED_DATA_INCREASE, NON_DUP_MARKER},
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::account_info::Account;
+
+/// Size of a duplicated account entry (marker and padding).
+const DUPLICATE_LEN: usize = 8;
+
+/// Account entry of a serialized input buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InputAccount<'a> {
+    /// Account serialized in full.
+    Account(SerializedAccount<'a>),
+
+    /// Duplicate of the account at the given index.
+    Duplicate(usize),
+}
+
+/// Account serialized in an input buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SerializedAccount<'a> {
+    /// Indicates whether the transaction was signed by this account.
+    pub is_signer: bool,
+
+    /// Indicates whether the account is writable.
+    pub is_writable: bool,
+
+    /// Indicates whether this account represents a program.
+    pub executable: bool,
+
+    /// Public key of the account.
+    pub key: &'a Pubkey,
+
+    /// Program that owns this account.
+    pub owner: &'a Pubkey,
+
+    /// The lamports in the account.
+    pub lamports: u64,
+
+    /// Data of the account.
+    pub data: &'a [u8],
+}
+
+/// Iterator over the offsets of the account entries of an input buffer.
+pub struct AccountOffsets<'a> {
+    /// Serialized input buffer.
+    input: &'a [u8],
+
+    /// Offset of the next account entry.
+    offset: usize,
+
+    /// Number of remaining accounts.
+    remaining: usize,
+}
+
+impl Iterator for AccountOffsets<'_> {
+    type Item = Result<usize, ProgramError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let offset = self.offset;
+
+        match entry_len(self.input, offset) {
+            Ok(len) => {
+                self.offset += len;
+                self.remaining -= 1;
+                Some(Ok(offset))
+            }
+            Err(error) => {
+                // the remaining entries cannot be located
+                self.remaining = 0;
+                Some(Err(error))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some(self.remaining))
+    }
+}
+
+/// Returns the number of accounts of a serialized input buffer.
+///
+/// Returns `ProgramError::InvalidAccountData` if the buffer is too small.
+///
+/// # Arguments
+///
+/// * `input`: Serialized input buffer.
+#[inline(always)]
+pub fn num_accounts(input: &[u8]) -> Result<usize, ProgramError> {
+    read_len(input, 0)
+}
+
+/// Returns an iterator over the offsets of the account entries of a serialized
+/// input buffer.
+///
+/// Returns `ProgramError::InvalidAccountData` if the buffer is too small.
+///
+/// # Arguments
+///
+/// * `input`: Serialized input buffer.
+pub fn account_offsets(input: &[u8]) -> Result<AccountOffsets<'_>, ProgramError> {
+    Ok(AccountOffsets {
+        input,
+        offset: std::mem::size_of::<u64>(),
+        remaining: num_accounts(input)?,
+    })
+}
+
+/// Parses the account at the given index of a serialized input buffer.
+///
+/// Only the entries preceding the account are visited (to locate it), without
+/// deserializing them.
+///
+/// Returns `ProgramError::InvalidArgument` when the index is out of bounds and
+/// `ProgramError::InvalidAccountData` if the buffer is malformed.
+///
+/// # Arguments
+///
+/// * `input`: Serialized input buffer.
+/// * `index`: Index of the account.
+pub fn parse_account_at(input: &[u8], index: usize) -> Result<InputAccount<'_>, ProgramError> {
+    let offset = account_offsets(input)?
+        .nth(index)
+        .ok_or(ProgramError::InvalidArgument)??;
+
+    // -      0: duplicate marker (index of the original account)
+    // -      1: is signer
+    // -      2: is writable
+    // -      3: executable
+    // -   4..8: padding
+    // -  8..40: key
+    // - 40..72: owner
+    // - 72..80: lamports
+    // - 80..88: data length
+    // -    88..: data
+    let marker = *input.get(offset).ok_or(ProgramError::InvalidAccountData)?;
+
+    if marker != NON_DUP_MARKER {
+        return Ok(InputAccount::Duplicate(marker as usize));
+    }
+
+    let data_offset = checked_end(offset, std::mem::size_of::<Account>())?;
+    let header = input
+        .get(offset..data_offset)
+        .ok_or(ProgramError::InvalidAccountData)?;
+    let data_len = read_len(header, 80)?;
+
+    Ok(InputAccount::Account(SerializedAccount {
+        is_signer: header[1] != 0,
+        is_writable: header[2] != 0,
+        executable: header[3] != 0,
+        key: bytemuck::from_bytes(&header[8..40]),
+        owner: bytemuck::from_bytes(&header[40..72]),
+        lamports: read_u64(header, 72)?,
+        data: input
+            .get(data_offset..checked_end(data_offset, data_len)?)
+            .ok_or(ProgramError::InvalidAccountData)?,
+    }))
+}
+
+//-- Internal functions
+
+/// Returns the length of the account entry at the given offset.
+///
+/// The lengths are read from the buffer, so every offset is computed with checked
+/// arithmetic to reject malformed buffers.
+fn entry_len(input: &[u8], offset: usize) -> Result<usize, ProgramError> {
+    let marker = *input.get(offset).ok_or(ProgramError::InvalidAccountData)?;
+
+    let end = if marker != NON_DUP_MARKER {
+        checked_end(offset, DUPLICATE_LEN)?
+    } else {
+        let data_len = read_len(input, checked_end(offset, 80)?)?;
+        // the realloc region is followed by padding to align the rent epoch
+        let end = checked_end(offset, std::mem::size_of::<Account>())?;
+        let end = checked_end(checked_end(end, data_len)?, MAX_PERMITTED_DATA_INCREASE)?;
+        let end = checked_end(
+            end,
+            (BPF_ALIGN_OF_U128 - end % BPF_ALIGN_OF_U128) % BPF_ALIGN_OF_U128,
+        )?;
+        // rent epoch
+        checked_end(end, std::mem::size_of::<u64>())?
+    };
+
+    if end > input.len() {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(end - offset)
+}
+
+/// Returns the end of a range of `len` bytes at the given offset.
+///
+/// Returns `ProgramError::InvalidAccountData` if the end overflows.
+#[inline(always)]
+fn checked_end(offset: usize, len: usize) -> Result<usize, ProgramError> {
+    offset
+        .checked_add(len)
+        .ok_or(ProgramError::InvalidAccountData)
+}
+
+/// Reads a little-endian `u64` length at the given offset.
+///
+/// Returns `ProgramError::InvalidAccountData` if the length does not fit in a
+/// `usize`.
+#[inline(always)]
+fn read_len(input: &[u8], offset: usize) -> Result<usize, ProgramError> {
+    usize::try_from(read_u64(input, offset)?).map_err(|_| ProgramError::InvalidAccountData)
+}
+
+/// Reads a little-endian `u64` value at the given offset.
+#[inline(always)]
+fn read_u64(input: &[u8], offset: usize) -> Result<u64, ProgramError> {
+    input
+        .get(offset..checked_end(offset, std::mem::size_of::<u64>())?)
+        .map(|value| u64::from_le_bytes(value.try_into().unwrap()))
+        .ok_or(ProgramError::InvalidAccountData)
+}
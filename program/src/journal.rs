@@ -0,0 +1,177 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Journal of account data writes.
+
+use bytemuck::Pod;
+use solana_program::{
+    entrypoint::ProgramResult, log::sol_log_data, program_error::ProgramError,
+    program_memory::sol_memcpy,
+};
+
+use crate::AccountInfo;
+
+/// Wrapper of an account that records the byte ranges of its data mutated
+/// during the instruction.
+///
+/// The journal tracks up to `RANGES` disjoint ranges sorted by offset;
+/// overlapping and adjacent writes are merged into a single range, coalescing
+/// every range they bridge. When all ranges are in use, a new write extends the
+/// closest range to cover it, so the journal always includes every mutated byte.
+///
+/// The recorded changes can be emitted using [`Journal::emit`], giving indexers
+/// the state diff of an account without re-parsing its data.
+pub struct Journal<'a, const RANGES: usize> {
+    /// Account being written.
+    account: &'a AccountInfo,
+
+    /// Recorded ranges as `(offset, length)` pairs.
+    ranges: [(usize, usize); RANGES],
+
+    /// Number of recorded ranges.
+    len: usize,
+}
+
+impl<'a, const RANGES: usize> Journal<'a, RANGES> {
+    /// Creates a new journal for the account.
+    pub fn new(account: &'a AccountInfo) -> Self {
+        Self {
+            account,
+            ranges: [(0, 0); RANGES],
+            len: 0,
+        }
+    }
+
+    /// Returns the account of the journal.
+    #[inline(always)]
+    pub fn account(&self) -> &'a AccountInfo {
+        self.account
+    }
+
+    /// Returns the recorded ranges as `(offset, length)` pairs.
+    #[inline(always)]
+    pub fn ranges(&self) -> &[(usize, usize)] {
+        &self.ranges[..self.len]
+    }
+
+    /// Writes the bytes at the given offset of the account data and records
+    /// the range.
+    pub fn write(&mut self, offset: usize, bytes: &[u8]) -> ProgramResult {
+        let mut data = self.account.try_borrow_mut_data()?;
+
+        let destination = offset
+            .checked_add(bytes.len())
+            .and_then(|end| data.get_mut(offset..end))
+            .ok_or(ProgramError::AccountDataTooSmall)?;
+        sol_memcpy(destination, bytes, bytes.len());
+
+        self.record(offset, bytes.len());
+
+        Ok(())
+    }
+
+    /// Writes a value of type `T` at the given offset of the account data and
+    /// records the range.
+    pub fn write_at<T: Pod>(&mut self, offset: usize, value: T) -> ProgramResult {
+        self.account.write_at(offset, value)?;
+        self.record(offset, std::mem::size_of::<T>());
+
+        Ok(())
+    }
+
+    /// Records a range of the account data as mutated.
+    ///
+    /// This is used to record writes performed directly on the account data.
+    pub fn record(&mut self, offset: usize, len: usize) {
+        if len == 0 {
+            return;
+        }
+
+        let mut start = offset;
+        let mut end = offset.saturating_add(len);
+
+        // ranges are sorted and disjoint, so the first range ending at or after
+        // the write is the first one that can overlap (or be adjacent to) it
+        let first = self.ranges[..self.len]
+            .iter()
+            .position(|(offset, len)| offset + len >= start)
+            .unwrap_or(self.len);
+
+        let mut last = first;
+
+        while last < self.len && self.ranges[last].0 <= end {
+            start = start.min(self.ranges[last].0);
+            end = end.max(self.ranges[last].0 + self.ranges[last].1);
+            last += 1;
+        }
+
+        if last > first {
+            // coalesce all ranges covered by the write into the first one
+            self.ranges[first] = (start, end - start);
+            self.ranges.copy_within(last..self.len, first + 1);
+            self.len -= last - first - 1;
+        } else if self.len < RANGES {
+            self.ranges.copy_within(first..self.len, first + 1);
+            self.ranges[first] = (start, end - start);
+            self.len += 1;
+        } else if RANGES > 0 {
+            // extend the closest neighbouring range to cover the write; the
+            // ranges remain disjoint since the write lies between them
+            let index = if first == 0 {
+                0
+            } else if first == self.len {
+                first - 1
+            } else {
+                let (previous, previous_len) = self.ranges[first - 1];
+                let (next, _) = self.ranges[first];
+
+                if start - (previous + previous_len) <= next - end {
+                    first - 1
+                } else {
+                    first
+                }
+            };
+
+            let range = &mut self.ranges[index];
+            let start = range.0.min(start);
+            *range = (start, (range.0 + range.1).max(end) - start);
+        }
+    }
+
+    /// Emits the recorded changes using `sol_log_data`.
+    ///
+    /// Each range is emitted as a separate entry with the fields:
+    ///
+    /// * account public key
+    /// * offset and length of the range as little-endian `u32` values
+    /// * current bytes of the range
+    ///
+    /// Ranges beyond the current data length are truncated.
+    pub fn emit(&self) -> ProgramResult {
+        let data = self.account.try_borrow_data()?;
+
+        for (offset, len) in self.ranges() {
+            let start = (*offset).min(data.len());
+            let end = offset.saturating_add(*len).min(data.len());
+
+            let mut header = [0u8; 8];
+            header[0..4].copy_from_slice(&(start as u32).to_le_bytes());
+            header[4..8].copy_from_slice(&((end - start) as u32).to_le_bytes());
+
+            sol_log_data(&[self.account.key().as_ref(), &header, &data[start..end]]);
+        }
+
+        Ok(())
+    }
+}
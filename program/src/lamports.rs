@@ -0,0 +1,254 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Debug helpers to verify lamport invariants.
+//!
+//! The checks are only performed when the `debug-lamports` feature is enabled;
+//! otherwise the helpers compile to no-ops.
+
+use solana_program::entrypoint::ProgramResult;
+
+use crate::AccountInfo;
+
+/// Guard that verifies the change of an account's lamports.
+///
+/// The guard records the lamports of the account when it is created; the change is
+/// verified with [`LamportGuard::verify`], which logs the expected and actual delta
+/// and returns an error on a mismatch. A guard that is dropped without being
+/// verified performs the check and only logs the mismatch, since the error cannot be
+/// returned from `drop`.
+///
+/// # Examples
+///
+/// ```ignore
+/// // the recipient must receive exactly the refunded amount
+/// let guard = LamportGuard::expect_delta(recipient, refund as i64);
+///
+/// // ...
+///
+/// guard.verify()?;
+/// ```
+pub struct LamportGuard<'a> {
+    #[cfg(feature = "debug-lamports")]
+    account: &'a AccountInfo,
+
+    #[cfg(feature = "debug-lamports")]
+    lamports: u64,
+
+    #[cfg(feature = "debug-lamports")]
+    delta: i64,
+
+    /// Indicates whether the guard was verified.
+    #[cfg(feature = "debug-lamports")]
+    verified: std::cell::Cell<bool>,
+
+    #[cfg(not(feature = "debug-lamports"))]
+    _account: std::marker::PhantomData<&'a AccountInfo>,
+}
+
+impl<'a> LamportGuard<'a> {
+    /// Creates a guard expecting the lamports of the account to change by `delta`.
+    ///
+    /// # Arguments
+    ///
+    /// * `account`: Account to verify.
+    /// * `delta`: Expected change in lamports (positive for credits).
+    #[inline(always)]
+    pub fn expect_delta(account: &'a AccountInfo, delta: i64) -> Self {
+        #[cfg(feature = "debug-lamports")]
+        {
+            Self {
+                account,
+                lamports: unsafe { *account.unchecked_borrow_lamports() },
+                delta,
+                verified: std::cell::Cell::new(false),
+            }
+        }
+
+        #[cfg(not(feature = "debug-lamports"))]
+        {
+            let _ = (account, delta);
+
+            Self {
+                _account: std::marker::PhantomData,
+            }
+        }
+    }
+
+    /// Creates a guard expecting the lamports of the account to not change.
+    ///
+    /// # Arguments
+    ///
+    /// * `account`: Account to verify.
+    #[inline(always)]
+    pub fn expect_unchanged(account: &'a AccountInfo) -> Self {
+        Self::expect_delta(account, 0)
+    }
+
+    /// Verifies the lamports of the account.
+    ///
+    /// Returns `ProgramError::InvalidAccountData` if the lamports did not change
+    /// by the expected delta.
+    #[inline(always)]
+    pub fn verify(&self) -> ProgramResult {
+        #[cfg(feature = "debug-lamports")]
+        {
+            self.verified.set(true);
+
+            let lamports = unsafe { *self.account.unchecked_borrow_lamports() };
+            let delta = lamports as i128 - self.lamports as i128;
+
+            if delta != self.delta as i128 {
+                solana_program::msg!(
+                    "🔴 Unexpected lamports delta for {}: expected {:+}, found {:+}",
+                    self.account.key(),
+                    self.delta,
+                    delta
+                );
+                return Err(solana_program::program_error::ProgramError::InvalidAccountData);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for LamportGuard<'_> {
+    fn drop(&mut self) {
+        // the mismatch is logged by `verify`; the error cannot be returned
+        #[cfg(feature = "debug-lamports")]
+        if !self.verified.get() {
+            let _ = self.verify();
+        }
+    }
+}
+
+/// Snapshot of the lamports of the accounts of an instruction.
+///
+/// The snapshot is taken by the entrypoint before the instruction is processed and
+/// verified afterwards, checking that the total lamports of the writable accounts is
+/// conserved. CPIs (e.g., System program transfers) can only move lamports between
+/// accounts of the instruction, so when every account is tracked an imbalance is
+/// caused by lamports created or destroyed by the program itself, which the runtime
+/// rejects with an opaque error. On a mismatch, the change of every account is
+/// logged.
+///
+/// When the instruction has more accounts than the maximum number of accounts of
+/// the entrypoint, lamports moved by a CPI to or from an untracked account would be
+/// reported as a mismatch, so the check is skipped (and flagged in the logs).
+pub struct LamportSnapshot<const ACCOUNTS: usize> {
+    #[cfg(feature = "debug-lamports")]
+    lamports: [u64; ACCOUNTS],
+
+    /// Indicates whether every account of the instruction is tracked.
+    #[cfg(feature = "debug-lamports")]
+    complete: bool,
+}
+
+impl<const ACCOUNTS: usize> LamportSnapshot<ACCOUNTS> {
+    /// Creates a snapshot of the lamports of the accounts.
+    ///
+    /// # Arguments
+    ///
+    /// * `accounts`: Accounts of the instruction.
+    /// * `total_accounts`: Total number of accounts of the instruction, including
+    ///   the accounts exceeding the maximum of the entrypoint.
+    #[inline(always)]
+    pub fn new(accounts: &[AccountInfo], total_accounts: usize) -> Self {
+        #[cfg(feature = "debug-lamports")]
+        {
+            let mut lamports = [0; ACCOUNTS];
+
+            accounts
+                .iter()
+                .zip(lamports.iter_mut())
+                .for_each(|(account, lamports)| {
+                    *lamports = unsafe { *account.unchecked_borrow_lamports() };
+                });
+
+            Self {
+                lamports,
+                complete: total_accounts <= ACCOUNTS,
+            }
+        }
+
+        #[cfg(not(feature = "debug-lamports"))]
+        {
+            let _ = (accounts, total_accounts);
+            Self {}
+        }
+    }
+
+    /// Verifies that the total lamports of the writable accounts did not change.
+    ///
+    /// The check is skipped when not every account of the instruction is tracked.
+    ///
+    /// Returns `ProgramError::InvalidAccountData` if the total changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `accounts`: Accounts of the instruction.
+    #[inline(always)]
+    pub fn verify(&self, accounts: &[AccountInfo]) -> ProgramResult {
+        #[cfg(feature = "debug-lamports")]
+        if !self.complete {
+            solana_program::msg!(
+                "🟡 Lamports conservation not checked: more than {} accounts",
+                ACCOUNTS
+            );
+        } else {
+            let mut before = 0u128;
+            let mut after = 0u128;
+
+            for (i, account) in accounts.iter().enumerate().take(ACCOUNTS) {
+                // duplicated accounts are only accounted once
+                if !account.is_writable() || accounts[..i].iter().any(|a| a.raw == account.raw) {
+                    continue;
+                }
+
+                before += self.lamports[i] as u128;
+                after += unsafe { *account.unchecked_borrow_lamports() } as u128;
+            }
+
+            if before != after {
+                for (i, account) in accounts.iter().enumerate().take(ACCOUNTS) {
+                    let lamports = unsafe { *account.unchecked_borrow_lamports() };
+
+                    if account.is_writable() && lamports != self.lamports[i] {
+                        solana_program::msg!(
+                            "🟡 Lamports of {}: {} -> {} ({:+})",
+                            account.key(),
+                            self.lamports[i],
+                            lamports,
+                            lamports as i128 - self.lamports[i] as i128
+                        );
+                    }
+                }
+
+                solana_program::msg!(
+                    "🔴 Lamports not conserved: {} before, {} after",
+                    before,
+                    after
+                );
+
+                return Err(solana_program::program_error::ProgramError::InvalidAccountData);
+            }
+        }
+
+        #[cfg(not(feature = "debug-lamports"))]
+        let _ = accounts;
+
+        Ok(())
+    }
+}
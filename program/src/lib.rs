@@ -13,9 +13,43 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+/// Returns the offset of a field in a struct.
+///
+/// This is evaluated at compile time and does not require `core::mem::offset_of`,
+/// which is not available in the Rust version of the Solana toolchain.
+macro_rules! offset_of {
+    ( $type:ty, $field:ident ) => {{
+        let uninit = core::mem::MaybeUninit::<$type>::uninit();
+        let base = uninit.as_ptr();
+        #[allow(unused_unsafe)]
+        unsafe {
+            (core::ptr::addr_of!((*base).$field) as *const u8).offset_from(base as *const u8)
+                as usize
+        }
+    }};
+}
+
 pub mod account_info;
+pub mod associated_token;
+pub mod batch;
+pub mod compute_budget;
 pub mod cpi;
+pub mod dispatch;
+pub mod feature;
+pub mod heap;
+pub mod input;
+pub mod journal;
+pub mod lamports;
+pub mod loader;
+pub mod memo;
+pub mod owner;
+pub mod precompiles;
+pub mod readonly;
+pub mod roles;
 pub mod system;
+pub mod sysvars;
+pub mod token;
+pub mod token_2022;
 
 pub use account_info::*;
 
@@ -68,6 +102,8 @@ pub unsafe fn deserialize<'a, const MAX_ACCOUNTS: usize>(
 
             // MAGNETAR FIELDS: reset borrow state right before pushing
             (*account_info).borrow_state = 0b_0000_0000;
+            // record the original data length, which determines the realloc region
+            (*account_info).original_data_len = ((*account_info).data_len as u32).to_le_bytes();
 
             std::ptr::write(
                 accounts.add(i),
@@ -0,0 +1,121 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Upgradeable BPF loader helpers.
+
+use solana_program::{entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::AccountInfo;
+
+pub use solana_program::bpf_loader_upgradeable::ID;
+
+/// Size of the program account data.
+///
+/// The data is the `bincode` serialization of the `Program` state: a 4-byte
+/// tag followed by the address of the program data account.
+pub const PROGRAM_LEN: usize = 36;
+
+/// Size of the program data account metadata (before the program bytes).
+///
+/// The metadata is the `bincode` serialization of the `ProgramData` state: a
+/// 4-byte tag, the slot of the last deployment, and the optional upgrade
+/// authority (a 1-byte tag followed by the authority address).
+pub const PROGRAM_DATA_METADATA_LEN: usize = 45;
+
+/// Tag of the `Program` state.
+const PROGRAM_TAG: u32 = 2;
+
+/// Tag of the `ProgramData` state.
+const PROGRAM_DATA_TAG: u32 = 3;
+
+/// Returns the upgrade authority of a program.
+///
+/// The program data account must be the one referenced by the program account,
+/// which the loader derives from the program address. An immutable program
+/// returns `None`.
+///
+/// # Arguments
+///
+/// * `program`: Program account.
+/// * `program_data`: Program data account of the program.
+pub fn upgrade_authority(
+    program: &AccountInfo,
+    program_data: &AccountInfo,
+) -> Result<Option<Pubkey>, ProgramError> {
+    if program.owner() != &ID || program_data.owner() != &ID {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let data = program.try_borrow_data()?;
+
+    // -    0..4: state tag
+    // -   4..36: program data address
+    if data.len() < PROGRAM_LEN || read_tag(&data) != PROGRAM_TAG {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if &data[4..36] != program_data.key().as_ref() {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let data = program_data.try_borrow_data()?;
+
+    // -    0..4: state tag
+    // -   4..12: slot
+    // -      12: option tag
+    // -  13..45: upgrade authority address
+    if data.len() < PROGRAM_DATA_METADATA_LEN || read_tag(&data) != PROGRAM_DATA_TAG {
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    match data[12] {
+        0 => Ok(None),
+        1 => Ok(Some(Pubkey::try_from(&data[13..45]).unwrap())),
+        _ => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+/// Checks that the authority is the upgrade authority of a program and that it
+/// signed the transaction.
+///
+/// Returns `ProgramError::InvalidAccountData` if the program is not upgradeable
+/// and `ProgramError::MissingRequiredSignature` if the authority does not match
+/// or is not a signer.
+///
+/// # Arguments
+///
+/// * `program`: Program account.
+/// * `program_data`: Program data account of the program.
+/// * `authority`: Expected upgrade authority account.
+pub fn check_upgrade_authority(
+    program: &AccountInfo,
+    program_data: &AccountInfo,
+    authority: &AccountInfo,
+) -> ProgramResult {
+    match upgrade_authority(program, program_data)? {
+        Some(upgrade_authority)
+            if &upgrade_authority == authority.key() && authority.is_signer() =>
+        {
+            Ok(())
+        }
+        Some(_) => Err(ProgramError::MissingRequiredSignature),
+        None => Err(ProgramError::InvalidAccountData),
+    }
+}
+
+/// Reads the little-endian `u32` state tag.
+#[inline(always)]
+fn read_tag(data: &[u8]) -> u32 {
+    u32::from_le_bytes(data[..4].try_into().unwrap())
+}
@@ -0,0 +1,133 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Memo program introspection and CPI functions.
+
+use solana_program::{
+    entrypoint::ProgramResult, program_error::ProgramError, pubkey, pubkey::Pubkey,
+};
+
+use crate::{
+    cpi::{invoke_signed_c, CAccountInfo, CAccountMeta, CInstruction},
+    sysvars::instructions::{Instructions, IntrospectedInstruction},
+    AccountInfo,
+};
+
+/// Memo program id.
+pub const ID: Pubkey = pubkey!("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr");
+
+/// Legacy (v1) Memo program id.
+pub const LEGACY_ID: Pubkey = pubkey!("Memo1UhkJRfHyvLMcVucJwxXeuD728EqVDDwQDxFMNo");
+
+/// Maximum length of a memo.
+///
+/// A memo cannot be larger than a transaction packet (1232 bytes).
+pub const MAX_MEMO_LEN: usize = 1232;
+
+/// Maximum number of signers of a memo.
+pub const MAX_SIGNERS: usize = 16;
+
+/// Invokes the Memo program with the given memo.
+///
+/// The `signers` must sign the transaction; the Memo program fails if any of them
+/// is not a signer.
+///
+/// Returns `ProgramError::InvalidArgument` if the memo is longer than `MAX_MEMO_LEN`
+/// or there are more than `MAX_SIGNERS` signers.
+///
+/// # Arguments
+///
+/// * `text`: Memo to attach.
+/// * `signers`: Accounts that sign the memo.
+pub fn build_memo(text: &str, signers: &[&AccountInfo]) -> ProgramResult {
+    if text.len() > MAX_MEMO_LEN || signers.len() > MAX_SIGNERS {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    let mut instruction_accounts: [std::mem::MaybeUninit<CAccountMeta>; MAX_SIGNERS] =
+        unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+    let mut account_infos: [std::mem::MaybeUninit<CAccountInfo>; MAX_SIGNERS] =
+        unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+
+    signers.iter().enumerate().for_each(|(i, signer)| {
+        instruction_accounts[i] =
+            std::mem::MaybeUninit::new(CAccountMeta::readonly_signer(signer.key()));
+        account_infos[i] = std::mem::MaybeUninit::new((*signer).into());
+    });
+
+    let instruction = CInstruction {
+        program_id: &ID,
+        accounts: instruction_accounts.as_ptr() as *const CAccountMeta,
+        accounts_len: signers.len() as u64,
+        data: text.as_ptr(),
+        data_len: text.len() as u64,
+    };
+
+    invoke_signed_c(
+        &instruction,
+        unsafe {
+            std::slice::from_raw_parts(account_infos.as_ptr() as *const CAccountInfo, signers.len())
+        },
+        &[],
+    )
+}
+
+/// Returns the memo of the first Memo program instruction of the transaction.
+///
+/// Returns `ProgramError::InvalidInstructionData` if the memo is not valid UTF-8.
+///
+/// # Arguments
+///
+/// * `instructions`: Instructions sysvar.
+pub fn find_memo<'a>(instructions: &'a Instructions) -> Result<Option<&'a str>, ProgramError> {
+    for index in 0..instructions.num_instructions() {
+        if let Some(memo) = memo(instructions.load_instruction_at(index)?)? {
+            return Ok(Some(memo));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Returns the memo of the instruction immediately preceding the current
+/// instruction, if it is a Memo program instruction.
+///
+/// This follows the convention of the Token-2022 memo transfer extension, which
+/// requires the memo to be the instruction before the transfer.
+///
+/// Returns `ProgramError::InvalidInstructionData` if the memo is not valid UTF-8.
+///
+/// # Arguments
+///
+/// * `instructions`: Instructions sysvar.
+pub fn preceding_memo<'a>(instructions: &'a Instructions) -> Result<Option<&'a str>, ProgramError> {
+    match instructions.current_index().checked_sub(1) {
+        Some(index) => memo(instructions.load_instruction_at(index)?),
+        None => Ok(None),
+    }
+}
+
+/// Returns the memo of the instruction if it is a Memo program instruction.
+#[inline(always)]
+fn memo<'a>(instruction: IntrospectedInstruction<'a>) -> Result<Option<&'a str>, ProgramError> {
+    let program_id = instruction.program_id();
+
+    if program_id != &ID && program_id != &LEGACY_ID {
+        return Ok(None);
+    }
+
+    std::str::from_utf8(instruction.data())
+        .map(Some)
+        .map_err(|_| ProgramError::InvalidInstructionData)
+}
@@ -0,0 +1,67 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Account owner validation.
+
+use solana_program::{entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::AccountInfo;
+
+/// Expected owner of an account.
+pub struct ExpectedOwner<'a> {
+    /// Name of the account, logged when the owner does not match.
+    pub name: &'a str,
+
+    /// Account to validate (`None` for an optional account that was not provided).
+    pub account: Option<&'a AccountInfo>,
+
+    /// Expected owner of the account.
+    pub owner: &'a Pubkey,
+}
+
+impl<'a> ExpectedOwner<'a> {
+    /// Creates a new `ExpectedOwner`.
+    #[inline(always)]
+    pub const fn new(name: &'a str, account: &'a AccountInfo, owner: &'a Pubkey) -> Self {
+        Self {
+            name,
+            account: Some(account),
+            owner,
+        }
+    }
+}
+
+/// Validates the owners of the accounts in a single pass.
+///
+/// Returns `ProgramError::InvalidAccountOwner` for the first account that is not
+/// owned by its expected owner, logging the name of the account when the `logging`
+/// feature is enabled. Accounts that were not provided are skipped.
+///
+/// # Arguments
+///
+/// * `expected`: Accounts and their expected owners.
+pub fn check_owners(expected: &[ExpectedOwner]) -> ProgramResult {
+    for expected in expected {
+        if let Some(account) = expected.account {
+            if account.owner() != expected.owner {
+                #[cfg(feature = "logging")]
+                solana_program::msg!("🔴 Account '{}' has an invalid owner", expected.name);
+
+                return Err(ProgramError::InvalidAccountOwner);
+            }
+        }
+    }
+
+    Ok(())
+}
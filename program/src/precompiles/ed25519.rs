@@ -0,0 +1,61 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Ed25519 precompile instructions.
+//!
+//! The instruction data is serialized as:
+//!
+//! * `u8` number of signatures
+//! * `u8` padding
+//! * [`SignatureOffsets`](super::SignatureOffsets) of each signature
+//! * public keys, signatures and messages referenced by the offsets
+
+use solana_program::{entrypoint::ProgramResult, pubkey::Pubkey};
+
+use super::{Precompile, PrecompileInstruction, PrecompileSignature};
+use crate::sysvars::instructions::Instructions;
+
+pub use super::SIGNATURE_LEN;
+pub use solana_program::ed25519_program::ID;
+
+/// Size of a public key.
+pub const PUBKEY_LEN: usize = 32;
+
+/// Ed25519 precompile.
+pub struct Ed25519;
+
+impl Precompile<PUBKEY_LEN> for Ed25519 {
+    const ID: Pubkey = ID;
+}
+
+/// Zero-copy view over an Ed25519 precompile instruction.
+pub type Ed25519Instruction<'a> = PrecompileInstruction<'a, Ed25519, PUBKEY_LEN>;
+
+/// Ed25519 signature verified by the precompile.
+pub type Ed25519Signature<'a> = PrecompileSignature<'a, PUBKEY_LEN>;
+
+/// Verifies that the transaction includes an Ed25519 signature of the message by
+/// the given public key.
+///
+/// Returns `ProgramError::MissingRequiredSignature` if no Ed25519 precompile
+/// instruction of the transaction verifies such signature.
+///
+/// # Arguments
+///
+/// * `instructions`: Instructions sysvar.
+/// * `public_key`: Public key of the expected signer.
+/// * `message`: Expected signed message.
+pub fn verify(instructions: &Instructions, public_key: &Pubkey, message: &[u8]) -> ProgramResult {
+    super::verify::<Ed25519, PUBKEY_LEN>(instructions, &public_key.to_bytes(), message)
+}
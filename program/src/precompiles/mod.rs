@@ -0,0 +1,260 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Zero-copy parsers for precompile instructions.
+//!
+//! Precompile instructions are verified by the runtime before the transaction is
+//! executed, so a program can rely on the presence of a precompile instruction in
+//! the transaction (found through the instructions sysvar) as proof that its
+//! signatures are valid.
+
+pub mod ed25519;
+pub mod secp256k1;
+pub mod secp256r1;
+
+use std::marker::PhantomData;
+
+use solana_program::{entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey};
+
+use crate::sysvars::instructions::Instructions;
+
+/// Index used by the signature offsets to refer to the precompile instruction itself.
+pub const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Size of a signature of the Ed25519 and Secp256r1 precompiles.
+pub const SIGNATURE_LEN: usize = 64;
+
+/// Offset of the signature offsets in the instruction data.
+const OFFSETS_START: usize = 2;
+
+/// Precompile whose instructions use [`SignatureOffsets`] to reference public keys
+/// of `PUBKEY_LEN` bytes.
+pub trait Precompile<const PUBKEY_LEN: usize> {
+    /// Program id of the precompile.
+    const ID: Pubkey;
+}
+
+/// Zero-copy view over a precompile instruction using [`SignatureOffsets`].
+///
+/// The instruction data is serialized as:
+///
+/// * `u8` number of signatures
+/// * `u8` padding
+/// * [`SignatureOffsets`] of each signature
+/// * public keys, signatures and messages referenced by the offsets
+pub struct PrecompileInstruction<'a, P, const PUBKEY_LEN: usize> {
+    /// Instructions of the transaction.
+    instructions: &'a Instructions<'a>,
+
+    /// Instruction data of the precompile instruction.
+    data: &'a [u8],
+
+    /// Precompile of the instruction.
+    precompile: PhantomData<P>,
+}
+
+/// Signature verified by a precompile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PrecompileSignature<'a, const PUBKEY_LEN: usize> {
+    /// Public key of the signer.
+    pub public_key: &'a [u8; PUBKEY_LEN],
+
+    /// Signature.
+    pub signature: &'a [u8; SIGNATURE_LEN],
+
+    /// Signed message.
+    pub message: &'a [u8],
+}
+
+impl<'a, P: Precompile<PUBKEY_LEN>, const PUBKEY_LEN: usize>
+    PrecompileInstruction<'a, P, PUBKEY_LEN>
+{
+    /// Creates a view over the instruction at the given index.
+    ///
+    /// Returns `ProgramError::IncorrectProgramId` if the instruction is not an
+    /// instruction of the precompile.
+    pub fn try_from(
+        instructions: &'a Instructions<'a>,
+        index: usize,
+    ) -> Result<Self, ProgramError> {
+        let instruction = instructions.load_instruction_at(index)?;
+
+        if instruction.program_id() != &P::ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        Ok(Self {
+            instructions,
+            data: instruction.data(),
+            precompile: PhantomData,
+        })
+    }
+
+    /// Returns the number of signatures verified by the instruction.
+    #[inline(always)]
+    pub fn num_signatures(&self) -> usize {
+        self.data.first().copied().unwrap_or_default() as usize
+    }
+
+    /// Returns the signature at the given index.
+    ///
+    /// Returns `ProgramError::InvalidInstructionData` if the index is out of
+    /// bounds or the offsets do not refer to valid data.
+    pub fn signature_at(
+        &self,
+        index: usize,
+    ) -> Result<PrecompileSignature<'a, PUBKEY_LEN>, ProgramError> {
+        if index >= self.num_signatures() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let offsets = SignatureOffsets::parse(
+            &self.data[(OFFSETS_START + index * SignatureOffsets::LEN).min(self.data.len())..],
+        )?;
+
+        let public_key = self.data_at(
+            offsets.public_key_instruction_index,
+            offsets.public_key_offset,
+            PUBKEY_LEN,
+        )?;
+        let signature = self.data_at(
+            offsets.signature_instruction_index,
+            offsets.signature_offset,
+            SIGNATURE_LEN,
+        )?;
+        let message = self.data_at(
+            offsets.message_instruction_index,
+            offsets.message_data_offset,
+            offsets.message_data_size as usize,
+        )?;
+
+        Ok(PrecompileSignature {
+            public_key: public_key.try_into().unwrap(),
+            signature: signature.try_into().unwrap(),
+            message,
+        })
+    }
+
+    /// Returns the bytes referenced by an instruction index and offset.
+    #[inline(always)]
+    fn data_at(&self, index: u16, offset: u16, len: usize) -> Result<&'a [u8], ProgramError> {
+        instruction_data(
+            self.instructions,
+            self.data,
+            (index != CURRENT_INSTRUCTION).then_some(index as usize),
+            offset as usize,
+            len,
+        )
+    }
+}
+
+/// Signature offsets used by the Ed25519 and Secp256r1 precompiles.
+///
+/// The offsets are serialized as little-endian `u16` values in the order of the fields.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SignatureOffsets {
+    /// Offset of the signature.
+    pub signature_offset: u16,
+
+    /// Index of the instruction containing the signature.
+    pub signature_instruction_index: u16,
+
+    /// Offset of the public key.
+    pub public_key_offset: u16,
+
+    /// Index of the instruction containing the public key.
+    pub public_key_instruction_index: u16,
+
+    /// Offset of the message.
+    pub message_data_offset: u16,
+
+    /// Size of the message.
+    pub message_data_size: u16,
+
+    /// Index of the instruction containing the message.
+    pub message_instruction_index: u16,
+}
+
+impl SignatureOffsets {
+    /// Size of the serialized offsets.
+    pub const LEN: usize = 14;
+
+    /// Parses the offsets from the serialized bytes.
+    fn parse(data: &[u8]) -> Result<Self, ProgramError> {
+        let data = data
+            .get(..Self::LEN)
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        let field = |index: usize| u16::from_le_bytes([data[index * 2], data[index * 2 + 1]]);
+
+        Ok(Self {
+            signature_offset: field(0),
+            signature_instruction_index: field(1),
+            public_key_offset: field(2),
+            public_key_instruction_index: field(3),
+            message_data_offset: field(4),
+            message_data_size: field(5),
+            message_instruction_index: field(6),
+        })
+    }
+}
+
+/// Returns the `len` bytes at `offset` of the data of an instruction.
+///
+/// When `instruction_index` is `None`, the bytes are read from the data of the
+/// precompile instruction itself.
+fn instruction_data<'a>(
+    instructions: &'a Instructions<'a>,
+    precompile_data: &'a [u8],
+    instruction_index: Option<usize>,
+    offset: usize,
+    len: usize,
+) -> Result<&'a [u8], ProgramError> {
+    let data = match instruction_index {
+        Some(index) => instructions.load_instruction_at(index)?.data(),
+        None => precompile_data,
+    };
+
+    data.get(offset..offset + len)
+        .ok_or(ProgramError::InvalidInstructionData)
+}
+
+/// Verifies that the transaction includes a signature of the message by the given
+/// public key in an instruction of the precompile.
+///
+/// Returns `ProgramError::MissingRequiredSignature` if no instruction of the
+/// precompile verifies such signature.
+fn verify<P: Precompile<PUBKEY_LEN>, const PUBKEY_LEN: usize>(
+    instructions: &Instructions,
+    public_key: &[u8; PUBKEY_LEN],
+    message: &[u8],
+) -> ProgramResult {
+    for index in 0..instructions.num_instructions() {
+        let instruction =
+            match PrecompileInstruction::<P, PUBKEY_LEN>::try_from(instructions, index) {
+                Ok(instruction) => instruction,
+                Err(ProgramError::IncorrectProgramId) => continue,
+                Err(error) => return Err(error),
+            };
+
+        for signature in 0..instruction.num_signatures() {
+            let signature = instruction.signature_at(signature)?;
+
+            if signature.public_key == public_key && signature.message == message {
+                return Ok(());
+            }
+        }
+    }
+
+    Err(ProgramError::MissingRequiredSignature)
+}
@@ -0,0 +1,231 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Secp256k1 precompile instructions.
+//!
+//! The precompile recovers the Ethereum address of the signer of each message
+//! (hashed with Keccak-256) and checks it against the expected address. The
+//! instruction data is serialized as:
+//!
+//! * `u8` number of signatures
+//! * [`Secp256k1SignatureOffsets`] of each signature
+//! * Ethereum addresses, signatures (followed by the recovery id) and messages
+//!   referenced by the offsets
+
+use solana_program::{entrypoint::ProgramResult, program_error::ProgramError};
+
+use super::instruction_data;
+use crate::sysvars::instructions::Instructions;
+
+pub use solana_program::secp256k1_program::ID;
+
+/// Size of an Ethereum address.
+pub const ETH_ADDRESS_LEN: usize = 20;
+
+/// Size of a signature (without the recovery id).
+pub const SIGNATURE_LEN: usize = 64;
+
+/// Offset of the signature offsets in the instruction data.
+const OFFSETS_START: usize = 1;
+
+/// Signature offsets used by the Secp256k1 precompile.
+///
+/// Offsets are serialized as little-endian `u16` values and instruction indices as
+/// `u8` values, in the order of the fields. Instruction indices refer to the
+/// instructions of the transaction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Secp256k1SignatureOffsets {
+    /// Offset of the signature (followed by the recovery id).
+    pub signature_offset: u16,
+
+    /// Index of the instruction containing the signature.
+    pub signature_instruction_index: u8,
+
+    /// Offset of the Ethereum address.
+    pub eth_address_offset: u16,
+
+    /// Index of the instruction containing the Ethereum address.
+    pub eth_address_instruction_index: u8,
+
+    /// Offset of the message.
+    pub message_data_offset: u16,
+
+    /// Size of the message.
+    pub message_data_size: u16,
+
+    /// Index of the instruction containing the message.
+    pub message_instruction_index: u8,
+}
+
+impl Secp256k1SignatureOffsets {
+    /// Size of the serialized offsets.
+    pub const LEN: usize = 11;
+
+    /// Parses the offsets from the serialized bytes.
+    fn parse(data: &[u8]) -> Result<Self, ProgramError> {
+        let data = data
+            .get(..Self::LEN)
+            .ok_or(ProgramError::InvalidInstructionData)?;
+        let field = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+
+        Ok(Self {
+            signature_offset: field(0),
+            signature_instruction_index: data[2],
+            eth_address_offset: field(3),
+            eth_address_instruction_index: data[5],
+            message_data_offset: field(6),
+            message_data_size: field(8),
+            message_instruction_index: data[10],
+        })
+    }
+}
+
+/// Zero-copy view over a Secp256k1 precompile instruction.
+pub struct Secp256k1Instruction<'a> {
+    /// Instructions of the transaction.
+    instructions: &'a Instructions<'a>,
+
+    /// Instruction data of the precompile instruction.
+    data: &'a [u8],
+}
+
+/// Secp256k1 signature verified by the precompile.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Secp256k1Signature<'a> {
+    /// Ethereum address of the signer.
+    pub eth_address: &'a [u8; ETH_ADDRESS_LEN],
+
+    /// Signature.
+    pub signature: &'a [u8; SIGNATURE_LEN],
+
+    /// Recovery id of the signature.
+    pub recovery_id: u8,
+
+    /// Signed message.
+    pub message: &'a [u8],
+}
+
+impl<'a> Secp256k1Instruction<'a> {
+    /// Creates a view over the instruction at the given index.
+    ///
+    /// Returns `ProgramError::IncorrectProgramId` if the instruction is not a
+    /// Secp256k1 precompile instruction.
+    pub fn try_from(
+        instructions: &'a Instructions<'a>,
+        index: usize,
+    ) -> Result<Self, ProgramError> {
+        let instruction = instructions.load_instruction_at(index)?;
+
+        if instruction.program_id() != &ID {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        Ok(Self {
+            instructions,
+            data: instruction.data(),
+        })
+    }
+
+    /// Returns the number of signatures verified by the instruction.
+    #[inline(always)]
+    pub fn num_signatures(&self) -> usize {
+        self.data.first().copied().unwrap_or_default() as usize
+    }
+
+    /// Returns the signature at the given index.
+    ///
+    /// Returns `ProgramError::InvalidInstructionData` if the index is out of
+    /// bounds or the offsets do not refer to valid data.
+    pub fn signature_at(&self, index: usize) -> Result<Secp256k1Signature<'a>, ProgramError> {
+        if index >= self.num_signatures() {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        let offsets = Secp256k1SignatureOffsets::parse(
+            &self.data
+                [(OFFSETS_START + index * Secp256k1SignatureOffsets::LEN).min(self.data.len())..],
+        )?;
+
+        let eth_address = self.data_at(
+            offsets.eth_address_instruction_index,
+            offsets.eth_address_offset,
+            ETH_ADDRESS_LEN,
+        )?;
+        // the recovery id follows the signature
+        let signature = self.data_at(
+            offsets.signature_instruction_index,
+            offsets.signature_offset,
+            SIGNATURE_LEN + 1,
+        )?;
+        let message = self.data_at(
+            offsets.message_instruction_index,
+            offsets.message_data_offset,
+            offsets.message_data_size as usize,
+        )?;
+
+        Ok(Secp256k1Signature {
+            eth_address: eth_address.try_into().unwrap(),
+            signature: signature[..SIGNATURE_LEN].try_into().unwrap(),
+            recovery_id: signature[SIGNATURE_LEN],
+            message,
+        })
+    }
+
+    /// Returns the bytes referenced by an instruction index and offset.
+    #[inline(always)]
+    fn data_at(&self, index: u8, offset: u16, len: usize) -> Result<&'a [u8], ProgramError> {
+        instruction_data(
+            self.instructions,
+            self.data,
+            Some(index as usize),
+            offset as usize,
+            len,
+        )
+    }
+}
+
+/// Verifies that the transaction includes a Secp256k1 signature of the message by
+/// the given Ethereum address.
+///
+/// Returns `ProgramError::MissingRequiredSignature` if no Secp256k1 precompile
+/// instruction of the transaction verifies such signature.
+///
+/// # Arguments
+///
+/// * `instructions`: Instructions sysvar.
+/// * `eth_address`: Ethereum address of the expected signer.
+/// * `message`: Expected signed message.
+pub fn verify(
+    instructions: &Instructions,
+    eth_address: &[u8; ETH_ADDRESS_LEN],
+    message: &[u8],
+) -> ProgramResult {
+    for index in 0..instructions.num_instructions() {
+        let instruction = match Secp256k1Instruction::try_from(instructions, index) {
+            Ok(instruction) => instruction,
+            Err(ProgramError::IncorrectProgramId) => continue,
+            Err(error) => return Err(error),
+        };
+
+        for signature in 0..instruction.num_signatures() {
+            let signature = instruction.signature_at(signature)?;
+
+            if signature.eth_address == eth_address && signature.message == message {
+                return Ok(());
+            }
+        }
+    }
+
+    Err(ProgramError::MissingRequiredSignature)
+}
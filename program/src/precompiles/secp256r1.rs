@@ -0,0 +1,69 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Secp256r1 precompile instructions.
+//!
+//! The precompile verifies P-256 signatures (e.g., used by passkeys and WebAuthn
+//! authenticators) of compressed public keys. The instruction data is serialized
+//! as:
+//!
+//! * `u8` number of signatures
+//! * `u8` padding
+//! * [`SignatureOffsets`](super::SignatureOffsets) of each signature
+//! * public keys, signatures and messages referenced by the offsets
+
+use solana_program::{entrypoint::ProgramResult, pubkey, pubkey::Pubkey};
+
+use super::{Precompile, PrecompileInstruction, PrecompileSignature};
+use crate::sysvars::instructions::Instructions;
+
+pub use super::SIGNATURE_LEN;
+
+/// Secp256r1 precompile program id.
+pub const ID: Pubkey = pubkey!("Secp256r1SigVerify1111111111111111111111111");
+
+/// Size of a (compressed) public key.
+pub const PUBKEY_LEN: usize = 33;
+
+/// Secp256r1 precompile.
+pub struct Secp256r1;
+
+impl Precompile<PUBKEY_LEN> for Secp256r1 {
+    const ID: Pubkey = ID;
+}
+
+/// Zero-copy view over a Secp256r1 precompile instruction.
+pub type Secp256r1Instruction<'a> = PrecompileInstruction<'a, Secp256r1, PUBKEY_LEN>;
+
+/// Secp256r1 signature verified by the precompile.
+pub type Secp256r1Signature<'a> = PrecompileSignature<'a, PUBKEY_LEN>;
+
+/// Verifies that the transaction includes a Secp256r1 signature of the message by
+/// the given public key.
+///
+/// Returns `ProgramError::MissingRequiredSignature` if no Secp256r1 precompile
+/// instruction of the transaction verifies such signature.
+///
+/// # Arguments
+///
+/// * `instructions`: Instructions sysvar.
+/// * `public_key`: Compressed public key of the expected signer.
+/// * `message`: Expected signed message.
+pub fn verify(
+    instructions: &Instructions,
+    public_key: &[u8; PUBKEY_LEN],
+    message: &[u8],
+) -> ProgramResult {
+    super::verify::<Secp256r1, PUBKEY_LEN>(instructions, public_key, message)
+}
@@ -0,0 +1,103 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Read-only view of an account.
+
+use bytemuck::Pod;
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{AccountInfo, Ref};
+
+/// Wrapper of a read-only account.
+///
+/// The wrapper only exposes the methods of [`AccountInfo`] that do not modify the
+/// account, so functions taking a `ReadonlyAccount` cannot write to it. It can only
+/// be created from an account that is not writable.
+#[derive(Clone, Copy)]
+pub struct ReadonlyAccount<'a> {
+    /// Read-only account.
+    account: &'a AccountInfo,
+}
+
+impl<'a> ReadonlyAccount<'a> {
+    /// Public key of the account.
+    #[inline(always)]
+    pub fn key(&self) -> &'a Pubkey {
+        self.account.key()
+    }
+
+    /// Program that owns this account.
+    #[inline(always)]
+    pub fn owner(&self) -> &'a Pubkey {
+        self.account.owner()
+    }
+
+    /// Indicates whether the transaction was signed by this account.
+    #[inline(always)]
+    pub fn is_signer(&self) -> bool {
+        self.account.is_signer()
+    }
+
+    /// Indicates whether this account represents a program.
+    #[inline(always)]
+    pub fn executable(&self) -> bool {
+        self.account.executable()
+    }
+
+    /// Returns the size of the data in the account.
+    #[inline(always)]
+    pub fn data_len(&self) -> usize {
+        self.account.data_len()
+    }
+
+    /// Indicates whether the account data is empty.
+    #[inline(always)]
+    pub fn data_is_empty(&self) -> bool {
+        self.account.data_is_empty()
+    }
+
+    /// Tries to get a read-only reference to the lamports in the account.
+    #[inline(always)]
+    pub fn try_borrow_lamports(&self) -> Result<Ref<'a, u64>, ProgramError> {
+        self.account.try_borrow_lamports()
+    }
+
+    /// Tries to get a read-only reference to the data in the account.
+    #[inline(always)]
+    pub fn try_borrow_data(&self) -> Result<Ref<'a, [u8]>, ProgramError> {
+        self.account.try_borrow_data()
+    }
+
+    /// Reads a value of type `T` at the given offset of the account data.
+    #[inline(always)]
+    pub fn read_at<T: Pod>(&self, offset: usize) -> Result<T, ProgramError> {
+        self.account.read_at(offset)
+    }
+}
+
+impl<'a> TryFrom<&'a AccountInfo> for ReadonlyAccount<'a> {
+    type Error = ProgramError;
+
+    /// Creates a `ReadonlyAccount` from an account.
+    ///
+    /// Returns `ProgramError::InvalidArgument` if the account is writable.
+    #[inline(always)]
+    fn try_from(account: &'a AccountInfo) -> Result<Self, Self::Error> {
+        if account.is_writable() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Ok(Self { account })
+    }
+}
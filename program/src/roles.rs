@@ -0,0 +1,133 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Account wrappers that encode the privileges of an account in its type.
+//!
+//! Each wrapper can only be created through a checked `try_from`, so functions
+//! taking a wrapper do not need to validate the account again.
+
+use solana_program::program_error::ProgramError;
+
+use crate::AccountInfo;
+
+/// Account that signed the transaction.
+#[derive(Clone, Copy)]
+pub struct Signer<'a>(&'a AccountInfo);
+
+impl<'a> Signer<'a> {
+    /// Returns the account.
+    #[inline(always)]
+    pub fn account(&self) -> &'a AccountInfo {
+        self.0
+    }
+}
+
+impl<'a> TryFrom<&'a AccountInfo> for Signer<'a> {
+    type Error = ProgramError;
+
+    /// Creates a `Signer` from an account.
+    ///
+    /// Returns `ProgramError::MissingRequiredSignature` if the account is not a signer.
+    #[inline(always)]
+    fn try_from(account: &'a AccountInfo) -> Result<Self, Self::Error> {
+        if !account.is_signer() {
+            return Err(ProgramError::MissingRequiredSignature);
+        }
+
+        Ok(Self(account))
+    }
+}
+
+impl core::ops::Deref for Signer<'_> {
+    type Target = AccountInfo;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+/// Writable account.
+#[derive(Clone, Copy)]
+pub struct Writable<'a>(&'a AccountInfo);
+
+impl<'a> Writable<'a> {
+    /// Returns the account.
+    #[inline(always)]
+    pub fn account(&self) -> &'a AccountInfo {
+        self.0
+    }
+}
+
+impl<'a> TryFrom<&'a AccountInfo> for Writable<'a> {
+    type Error = ProgramError;
+
+    /// Creates a `Writable` from an account.
+    ///
+    /// Returns `ProgramError::InvalidArgument` if the account is not writable.
+    #[inline(always)]
+    fn try_from(account: &'a AccountInfo) -> Result<Self, Self::Error> {
+        if !account.is_writable() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Ok(Self(account))
+    }
+}
+
+impl core::ops::Deref for Writable<'_> {
+    type Target = AccountInfo;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+/// Executable program account.
+#[derive(Clone, Copy)]
+pub struct Program<'a>(&'a AccountInfo);
+
+impl<'a> Program<'a> {
+    /// Returns the account.
+    #[inline(always)]
+    pub fn account(&self) -> &'a AccountInfo {
+        self.0
+    }
+}
+
+impl<'a> TryFrom<&'a AccountInfo> for Program<'a> {
+    type Error = ProgramError;
+
+    /// Creates a `Program` from an account.
+    ///
+    /// Returns `ProgramError::IncorrectProgramId` if the account is not executable.
+    #[inline(always)]
+    fn try_from(account: &'a AccountInfo) -> Result<Self, Self::Error> {
+        if !account.executable() {
+            return Err(ProgramError::IncorrectProgramId);
+        }
+
+        Ok(Self(account))
+    }
+}
+
+impl core::ops::Deref for Program<'_> {
+    type Target = AccountInfo;
+
+    #[inline(always)]
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
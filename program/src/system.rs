@@ -15,10 +15,21 @@
 
 //! System Program CPI functions.
 
-use solana_program::{pubkey::Pubkey, system_program};
+use solana_program::{
+    entrypoint::ProgramResult,
+    nonce::state::State as NonceState,
+    program_error::ProgramError,
+    pubkey::{Pubkey, MAX_SEEDS, MAX_SEED_LEN},
+    rent::Rent,
+    system_program,
+    sysvar::Sysvar,
+};
 
 use crate::{
-    cpi::{CAccountInfo, CAccountMeta, CInstruction, CSigner, CSignerSeed},
+    cpi::{
+        self, AsSigner, CAccountInfo, CAccountMeta, CInstruction, CSigner, CSignerSeed,
+        IntoAccountMetas,
+    },
     AccountInfo,
 };
 
@@ -37,8 +48,8 @@ pub fn create_account(
     lamports: u64,
     space: u64,
     owner: &Pubkey,
-) {
-    _create_account_signed(funder, account, lamports, space, owner, &[]);
+) -> ProgramResult {
+    _create_account_signed(funder, account, lamports, space, owner, &[])
 }
 
 /// Create a new account with a program signed instruction.
@@ -58,7 +69,7 @@ pub fn create_account_signed<const SEEDS: usize>(
     space: u64,
     owner: &Pubkey,
     signer_seeds: &[&[u8]; SEEDS],
-) {
+) -> ProgramResult {
     // signer seeds
     let mut seeds: [std::mem::MaybeUninit<CSignerSeed>; SEEDS] =
         unsafe { std::mem::MaybeUninit::uninit().assume_init() };
@@ -75,7 +86,225 @@ pub fn create_account_signed<const SEEDS: usize>(
         len: SEEDS as u64,
     }];
 
-    _create_account_signed(funder, account, lamports, space, owner, &signer);
+    _create_account_signed(funder, account, lamports, space, owner, &signer)
+}
+
+/// Create a new account with a program signed instruction using a slice of seeds.
+///
+/// This is useful when the number of seeds is only known at runtime (e.g., an
+/// optional bump seed).
+///
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seeds exceed `MAX_SEEDS`.
+///
+/// # Arguments
+///
+/// * `funder`: Funding account.
+/// * `account`: New account.
+/// * `lamports`: Number of lamports to transfer to the new account.
+/// * `space`: Number of bytes of memory to allocate.
+/// * `owner`: Address of program that will own the new account.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+pub fn create_account_signed_slice(
+    funder: &AccountInfo,
+    account: &AccountInfo,
+    lamports: u64,
+    space: u64,
+    owner: &Pubkey,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let seeds = cpi::slice_signer_seeds(signer_seeds)?;
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: signer_seeds.len() as u64,
+    }];
+
+    _create_account_signed(funder, account, lamports, space, owner, &signer)
+}
+
+/// Create a new account with an instruction signed by multiple program derived
+/// addresses (e.g., a funder PDA and the new account PDA).
+///
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seeds of a signer exceed
+/// `MAX_SEEDS`.
+///
+/// # Arguments
+///
+/// * `funder`: Funding account.
+/// * `account`: New account.
+/// * `lamports`: Number of lamports to transfer to the new account.
+/// * `space`: Number of bytes of memory to allocate.
+/// * `owner`: Address of program that will own the new account.
+/// * `signers_seeds`: Seeds of each signer of the instruction.
+pub fn create_account_multi_signed<const SIGNERS: usize>(
+    funder: &AccountInfo,
+    account: &AccountInfo,
+    lamports: u64,
+    space: u64,
+    owner: &Pubkey,
+    signers_seeds: &[&[&[u8]]; SIGNERS],
+) -> ProgramResult {
+    let seeds = cpi::multi_signer_seeds(signers_seeds)?;
+    let signers: [CSigner; SIGNERS] = std::array::from_fn(|i| CSigner {
+        seeds: seeds[i].as_ptr(),
+        len: signers_seeds[i].len() as u64,
+    });
+
+    _create_account_signed(funder, account, lamports, space, owner, &signers)
+}
+
+/// Create a new account funded with the minimum balance for rent exemption.
+///
+/// The rent is read through the `Rent` sysvar syscall.
+///
+/// # Arguments
+///
+/// * `funder`: Funding account.
+/// * `account`: New account.
+/// * `space`: Number of bytes of memory to allocate.
+/// * `owner`: Address of program that will own the new account.
+pub fn create_account_with_rent(
+    funder: &AccountInfo,
+    account: &AccountInfo,
+    space: u64,
+    owner: &Pubkey,
+) -> ProgramResult {
+    let lamports = Rent::get()?.minimum_balance(space as usize);
+    _create_account_signed(funder, account, lamports, space, owner, &[])
+}
+
+/// Create a new account funded with the minimum balance for rent exemption with a
+/// program signed instruction.
+///
+/// The rent is read through the `Rent` sysvar syscall.
+///
+/// # Arguments
+///
+/// * `funder`: Funding account.
+/// * `account`: New account.
+/// * `space`: Number of bytes of memory to allocate.
+/// * `owner`: Address of program that will own the new account.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+pub fn create_account_with_rent_signed<const SEEDS: usize>(
+    funder: &AccountInfo,
+    account: &AccountInfo,
+    space: u64,
+    owner: &Pubkey,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let lamports = Rent::get()?.minimum_balance(space as usize);
+
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _create_account_signed(funder, account, lamports, space, owner, &signer)
+}
+
+/// Create a new account funded with the minimum balance for rent exemption, or
+/// initialize it if it already holds lamports.
+///
+/// A `CreateAccount` instruction fails when the account already holds lamports
+/// (e.g., someone transferred lamports to the address in advance), in which case
+/// the account is topped up to the minimum balance for rent exemption, allocated and
+/// assigned instead. The rent is read through the `Rent` sysvar syscall.
+///
+/// # Arguments
+///
+/// * `funder`: Funding account.
+/// * `account`: New account.
+/// * `space`: Number of bytes of memory to allocate.
+/// * `owner`: Address of program that will own the new account.
+pub fn create_or_initialize(
+    funder: &AccountInfo,
+    account: &AccountInfo,
+    space: u64,
+    owner: &Pubkey,
+) -> ProgramResult {
+    _create_or_initialize_signed(funder, account, space, owner, &[])
+}
+
+/// Create a new account funded with the minimum balance for rent exemption, or
+/// initialize it if it already holds lamports, with a program signed instruction.
+///
+/// # Arguments
+///
+/// * `funder`: Funding account.
+/// * `account`: New account.
+/// * `space`: Number of bytes of memory to allocate.
+/// * `owner`: Address of program that will own the new account.
+/// * `signer_seeds`: Seeds used to sign the instructions.
+pub fn create_or_initialize_signed<const SEEDS: usize>(
+    funder: &AccountInfo,
+    account: &AccountInfo,
+    space: u64,
+    owner: &Pubkey,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _create_or_initialize_signed(funder, account, space, owner, &signer)
+}
+
+/// Create a new program derived account funded with the minimum balance for
+/// rent exemption.
+///
+/// The rent is read through the `Rent` sysvar syscall and the bump is appended
+/// to the seeds to sign the instruction.
+///
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seeds (including the bump)
+/// exceed `MAX_SEEDS`.
+///
+/// # Arguments
+///
+/// * `funder`: Funding account.
+/// * `account`: New account.
+/// * `seeds`: Seeds of the program derived address (without the bump).
+/// * `bump`: Bump seed of the program derived address.
+/// * `space`: Number of bytes of memory to allocate.
+/// * `owner`: Address of program that will own the new account.
+pub fn create_pda<const SEEDS: usize>(
+    funder: &AccountInfo,
+    account: &AccountInfo,
+    seeds: &[&[u8]; SEEDS],
+    bump: u8,
+    space: u64,
+    owner: &Pubkey,
+) -> ProgramResult {
+    if SEEDS >= MAX_SEEDS {
+        return Err(ProgramError::MaxSeedLengthExceeded);
+    }
+
+    let lamports = Rent::get()?.minimum_balance(space as usize);
+
+    // signer seeds (including the bump)
+    let mut signer_seeds: [std::mem::MaybeUninit<CSignerSeed>; MAX_SEEDS] =
+        unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+
+    seeds.iter().enumerate().for_each(|(i, seed)| {
+        signer_seeds[i] = std::mem::MaybeUninit::new(CSignerSeed {
+            seed: seed.as_ptr(),
+            len: seed.len() as u64,
+        })
+    });
+
+    let bump = [bump];
+    signer_seeds[SEEDS] = std::mem::MaybeUninit::new(CSignerSeed {
+        seed: bump.as_ptr(),
+        len: bump.len() as u64,
+    });
+
+    let signer = [CSigner {
+        seeds: signer_seeds.as_ptr() as *const CSignerSeed,
+        len: SEEDS as u64 + 1,
+    }];
+
+    _create_account_signed(funder, account, lamports, space, owner, &signer)
 }
 
 /// Transfer lamports between accounts.
@@ -85,104 +314,1525 @@ pub fn create_account_signed<const SEEDS: usize>(
 /// * `from`: Funding account.
 /// * `recipient`: Recipient account.
 /// * `amount`: Number of lamports to transfer.
-pub fn transfer(from: &AccountInfo, recipient: &AccountInfo, amount: u64) {
-    let instruction_accounts: [CAccountMeta; 2] = [from.into(), recipient.into()];
-
-    // -   0..4: instruction discriminator
-    // -  4..12: lamports amount
-    let mut instruction_data = [0; 12];
-    // transfer instruction has a '2' discriminator
-    instruction_data[0] = 2;
-    instruction_data[4..12].copy_from_slice(&amount.to_le_bytes());
+pub fn transfer(from: &AccountInfo, recipient: &AccountInfo, amount: u64) -> ProgramResult {
+    _transfer_signed(from, recipient, amount, &[])
+}
 
-    let instruction = CInstruction {
-        program_id: &system_program::ID,
-        accounts: instruction_accounts.as_ptr(),
-        accounts_len: instruction_accounts.len() as u64,
-        data: instruction_data.as_ptr(),
-        data_len: instruction_data.len() as u64,
-    };
+/// Transfer all lamports of an account, optionally keeping the minimum balance for
+/// rent exemption.
+///
+/// Lamports of accounts owned by the System program are transferred with a CPI, in
+/// which case the account must sign the instruction; otherwise the lamports are moved
+/// directly, which requires the account to be owned by the program. The rent is read
+/// through the `Rent` sysvar syscall when `rent_exempt` is set.
+///
+/// # Arguments
+///
+/// * `from`: Funding account.
+/// * `recipient`: Recipient account.
+/// * `rent_exempt`: Whether to keep the minimum balance for rent exemption of the
+///   funding account.
+pub fn transfer_all(
+    from: &AccountInfo,
+    recipient: &AccountInfo,
+    rent_exempt: bool,
+) -> ProgramResult {
+    _transfer_all_signed(from, recipient, rent_exempt, &[])
+}
 
-    // account infos and seeds
-    let account_infos: [CAccountInfo; 2] = [from.into(), recipient.into()];
-    let seeds: &[&[&[u8]]] = &[];
-
-    #[cfg(target_os = "solana")]
-    unsafe {
-        solana_program::syscalls::sol_invoke_signed_c(
-            &instruction as *const CInstruction as *const u8,
-            account_infos.as_ptr() as *const u8,
-            account_infos.len() as u64,
-            seeds.as_ptr() as *const u8,
-            seeds.len() as u64,
-        );
-    }
+/// Transfer all lamports of an account, optionally keeping the minimum balance for
+/// rent exemption, with a program signed instruction.
+///
+/// The signer seeds are only used when the lamports are transferred with a CPI, i.e.,
+/// when the account is owned by the System program.
+///
+/// # Arguments
+///
+/// * `from`: Funding account.
+/// * `recipient`: Recipient account.
+/// * `rent_exempt`: Whether to keep the minimum balance for rent exemption of the
+///   funding account.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+pub fn transfer_all_signed<const SEEDS: usize>(
+    from: &AccountInfo,
+    recipient: &AccountInfo,
+    rent_exempt: bool,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
 
-    // keep clippy happy
-    #[cfg(not(target_os = "solana"))]
-    core::hint::black_box(&(&instruction, &account_infos, &seeds));
+    _transfer_all_signed(from, recipient, rent_exempt, &signer)
 }
 
-//-- Internal functions
+/// Create a new account at an address derived from a base public key and a seed.
+///
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seed is longer than
+/// `MAX_SEED_LEN`.
+///
+/// # Arguments
+///
+/// * `funder`: Funding account.
+/// * `account`: New account.
+/// * `base`: Base account of the address derivation (must sign the instruction).
+/// * `seed`: Seed of the address derivation.
+/// * `lamports`: Number of lamports to transfer to the new account.
+/// * `space`: Number of bytes of memory to allocate.
+/// * `owner`: Address of program that will own the new account.
+pub fn create_account_with_seed(
+    funder: &AccountInfo,
+    account: &AccountInfo,
+    base: &AccountInfo,
+    seed: &str,
+    lamports: u64,
+    space: u64,
+    owner: &Pubkey,
+) -> ProgramResult {
+    _create_account_with_seed_signed(funder, account, base, seed, lamports, space, owner, &[])
+}
 
-/// Create a new account.
+/// Create a new account at an address derived from a base public key and a seed
+/// with a program signed instruction.
 ///
-/// This function is used to create a new account either with or without a program
-/// signed instruction.
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seed is longer than
+/// `MAX_SEED_LEN`.
 ///
 /// # Arguments
 ///
 /// * `funder`: Funding account.
 /// * `account`: New account.
+/// * `base`: Base account of the address derivation (must sign the instruction).
+/// * `seed`: Seed of the address derivation.
 /// * `lamports`: Number of lamports to transfer to the new account.
 /// * `space`: Number of bytes of memory to allocate.
 /// * `owner`: Address of program that will own the new account.
-/// * `signer`: Seeds used to sign the instruction.
-fn _create_account_signed(
+/// * `signer_seeds`: Seeds used to sign the instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn create_account_with_seed_signed<const SEEDS: usize>(
     funder: &AccountInfo,
     account: &AccountInfo,
+    base: &AccountInfo,
+    seed: &str,
     lamports: u64,
     space: u64,
     owner: &Pubkey,
-    signer: &[CSigner],
-) {
-    let mut instruction_accounts: [CAccountMeta; 2] = [funder.into(), account.into()];
-    // account being created is always a signer
-    instruction_accounts[1].is_signer = true;
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
 
-    // -   0..4: instruction discriminator
-    // -  4..12: lamports
-    // - 12..20: account space
-    // - 20..52: owner pubkey
-    let mut instruction_data = [0; 52];
-    // create account instruction has a '0' discriminator
-    instruction_data[4..12].copy_from_slice(&lamports.to_le_bytes());
-    instruction_data[12..20].copy_from_slice(&space.to_le_bytes());
-    instruction_data[20..52].copy_from_slice(owner.as_ref());
+    _create_account_with_seed_signed(funder, account, base, seed, lamports, space, owner, &signer)
+}
 
-    let instruction = CInstruction {
-        program_id: &system_program::ID,
-        accounts: instruction_accounts.as_ptr(),
-        accounts_len: instruction_accounts.len() as u64,
-        data: instruction_data.as_ptr(),
-        data_len: instruction_data.len() as u64,
-    };
+/// Transfer lamports from an account at an address derived from a base public key
+/// and a seed.
+///
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seed is longer than
+/// `MAX_SEED_LEN`.
+///
+/// # Arguments
+///
+/// * `from`: Funding account.
+/// * `base`: Base account of the address derivation (must sign the instruction).
+/// * `recipient`: Recipient account.
+/// * `amount`: Number of lamports to transfer.
+/// * `seed`: Seed of the address derivation.
+/// * `owner`: Address of program used in the address derivation.
+pub fn transfer_with_seed(
+    from: &AccountInfo,
+    base: &AccountInfo,
+    recipient: &AccountInfo,
+    amount: u64,
+    seed: &str,
+    owner: &Pubkey,
+) -> ProgramResult {
+    _transfer_with_seed_signed(from, base, recipient, amount, seed, owner, &[])
+}
 
-    // account infos
-    let account_infos: [CAccountInfo; 2] = [funder.into(), account.into()];
+/// Transfer lamports from an account at an address derived from a base public key
+/// and a seed with a program signed instruction.
+///
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seed is longer than
+/// `MAX_SEED_LEN`.
+///
+/// # Arguments
+///
+/// * `from`: Funding account.
+/// * `base`: Base account of the address derivation (must sign the instruction).
+/// * `recipient`: Recipient account.
+/// * `amount`: Number of lamports to transfer.
+/// * `seed`: Seed of the address derivation.
+/// * `owner`: Address of program used in the address derivation.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+pub fn transfer_with_seed_signed<const SEEDS: usize>(
+    from: &AccountInfo,
+    base: &AccountInfo,
+    recipient: &AccountInfo,
+    amount: u64,
+    seed: &str,
+    owner: &Pubkey,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
 
-    #[cfg(target_os = "solana")]
-    unsafe {
-        solana_program::syscalls::sol_invoke_signed_c(
-            &instruction as *const CInstruction as *const u8,
-            account_infos.as_ptr() as *const u8,
-            account_infos.len() as u64,
-            signer.as_ptr() as *const u8,
-            signer.len() as u64,
-        );
-    }
+    _transfer_with_seed_signed(from, base, recipient, amount, seed, owner, &signer)
+}
+
+/// Allocate space for an account.
+///
+/// The account must not be owned by a program and have no data allocated.
+///
+/// # Arguments
+///
+/// * `account`: Account to allocate.
+/// * `space`: Number of bytes of memory to allocate.
+pub fn allocate(account: &AccountInfo, space: u64) -> ProgramResult {
+    _allocate_signed(account, space, &[])
+}
+
+/// Allocate space for an account with a program signed instruction.
+///
+/// # Arguments
+///
+/// * `account`: Account to allocate.
+/// * `space`: Number of bytes of memory to allocate.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+pub fn allocate_signed<const SEEDS: usize>(
+    account: &AccountInfo,
+    space: u64,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _allocate_signed(account, space, &signer)
+}
+
+/// Assign an account to a program.
+///
+/// # Arguments
+///
+/// * `account`: Account to assign.
+/// * `owner`: Address of program that will own the account.
+pub fn assign(account: &AccountInfo, owner: &Pubkey) -> ProgramResult {
+    _assign_signed(account, owner, &[])
+}
+
+/// Assign an account to a program with a program signed instruction.
+///
+/// # Arguments
+///
+/// * `account`: Account to assign.
+/// * `owner`: Address of program that will own the account.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+pub fn assign_signed<const SEEDS: usize>(
+    account: &AccountInfo,
+    owner: &Pubkey,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _assign_signed(account, owner, &signer)
+}
+
+/// Allocate space for an account at an address derived from a base public key and
+/// a seed.
+///
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seed is longer than
+/// `MAX_SEED_LEN`.
+///
+/// # Arguments
+///
+/// * `account`: Account to allocate.
+/// * `base`: Base account of the address derivation (must sign the instruction).
+/// * `seed`: Seed of the address derivation.
+/// * `space`: Number of bytes of memory to allocate.
+/// * `owner`: Address of program used in the address derivation.
+pub fn allocate_with_seed(
+    account: &AccountInfo,
+    base: &AccountInfo,
+    seed: &str,
+    space: u64,
+    owner: &Pubkey,
+) -> ProgramResult {
+    _allocate_with_seed_signed(account, base, seed, space, owner, &[])
+}
+
+/// Allocate space for an account at an address derived from a base public key and
+/// a seed with a program signed instruction.
+///
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seed is longer than
+/// `MAX_SEED_LEN`.
+///
+/// # Arguments
+///
+/// * `account`: Account to allocate.
+/// * `base`: Base account of the address derivation (must sign the instruction).
+/// * `seed`: Seed of the address derivation.
+/// * `space`: Number of bytes of memory to allocate.
+/// * `owner`: Address of program used in the address derivation.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+pub fn allocate_with_seed_signed<const SEEDS: usize>(
+    account: &AccountInfo,
+    base: &AccountInfo,
+    seed: &str,
+    space: u64,
+    owner: &Pubkey,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _allocate_with_seed_signed(account, base, seed, space, owner, &signer)
+}
+
+/// Assign an account at an address derived from a base public key and a seed to a
+/// program.
+///
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seed is longer than
+/// `MAX_SEED_LEN`.
+///
+/// # Arguments
+///
+/// * `account`: Account to assign.
+/// * `base`: Base account of the address derivation (must sign the instruction).
+/// * `seed`: Seed of the address derivation.
+/// * `owner`: Address of program that will own the account.
+pub fn assign_with_seed(
+    account: &AccountInfo,
+    base: &AccountInfo,
+    seed: &str,
+    owner: &Pubkey,
+) -> ProgramResult {
+    _assign_with_seed_signed(account, base, seed, owner, &[])
+}
+
+/// Assign an account at an address derived from a base public key and a seed to a
+/// program with a program signed instruction.
+///
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seed is longer than
+/// `MAX_SEED_LEN`.
+///
+/// # Arguments
+///
+/// * `account`: Account to assign.
+/// * `base`: Base account of the address derivation (must sign the instruction).
+/// * `seed`: Seed of the address derivation.
+/// * `owner`: Address of program that will own the account.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+pub fn assign_with_seed_signed<const SEEDS: usize>(
+    account: &AccountInfo,
+    base: &AccountInfo,
+    seed: &str,
+    owner: &Pubkey,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _assign_with_seed_signed(account, base, seed, owner, &signer)
+}
+
+/// Advance the stored blockhash of a durable nonce account.
+///
+/// # Arguments
+///
+/// * `nonce`: Nonce account.
+/// * `recent_blockhashes`: `RecentBlockhashes` sysvar account.
+/// * `authority`: Authority of the nonce account.
+pub fn advance_nonce_account(
+    nonce: &AccountInfo,
+    recent_blockhashes: &AccountInfo,
+    authority: &AccountInfo,
+) -> ProgramResult {
+    _advance_nonce_account_signed(nonce, recent_blockhashes, authority, &[])
+}
+
+/// Advance the stored blockhash of a durable nonce account with a program signed
+/// instruction.
+///
+/// # Arguments
+///
+/// * `nonce`: Nonce account.
+/// * `recent_blockhashes`: `RecentBlockhashes` sysvar account.
+/// * `authority`: Authority of the nonce account.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+pub fn advance_nonce_account_signed<const SEEDS: usize>(
+    nonce: &AccountInfo,
+    recent_blockhashes: &AccountInfo,
+    authority: &AccountInfo,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _advance_nonce_account_signed(nonce, recent_blockhashes, authority, &signer)
+}
+
+/// Withdraw lamports from a durable nonce account.
+///
+/// # Arguments
+///
+/// * `nonce`: Nonce account.
+/// * `recipient`: Recipient account.
+/// * `recent_blockhashes`: `RecentBlockhashes` sysvar account.
+/// * `rent`: `Rent` sysvar account.
+/// * `authority`: Authority of the nonce account.
+/// * `lamports`: Number of lamports to withdraw.
+pub fn withdraw_nonce_account(
+    nonce: &AccountInfo,
+    recipient: &AccountInfo,
+    recent_blockhashes: &AccountInfo,
+    rent: &AccountInfo,
+    authority: &AccountInfo,
+    lamports: u64,
+) -> ProgramResult {
+    _withdraw_nonce_account_signed(
+        nonce,
+        recipient,
+        recent_blockhashes,
+        rent,
+        authority,
+        lamports,
+        &[],
+    )
+}
+
+/// Withdraw lamports from a durable nonce account with a program signed
+/// instruction.
+///
+/// # Arguments
+///
+/// * `nonce`: Nonce account.
+/// * `recipient`: Recipient account.
+/// * `recent_blockhashes`: `RecentBlockhashes` sysvar account.
+/// * `rent`: `Rent` sysvar account.
+/// * `authority`: Authority of the nonce account.
+/// * `lamports`: Number of lamports to withdraw.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+pub fn withdraw_nonce_account_signed<const SEEDS: usize>(
+    nonce: &AccountInfo,
+    recipient: &AccountInfo,
+    recent_blockhashes: &AccountInfo,
+    rent: &AccountInfo,
+    authority: &AccountInfo,
+    lamports: u64,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _withdraw_nonce_account_signed(
+        nonce,
+        recipient,
+        recent_blockhashes,
+        rent,
+        authority,
+        lamports,
+        &signer,
+    )
+}
+
+/// Initialize a durable nonce account.
+///
+/// The account must be allocated with the size of a nonce account and funded with
+/// the minimum balance for rent exemption.
+///
+/// # Arguments
+///
+/// * `nonce`: Nonce account.
+/// * `recent_blockhashes`: `RecentBlockhashes` sysvar account.
+/// * `rent`: `Rent` sysvar account.
+/// * `authority`: Authority of the nonce account.
+pub fn initialize_nonce_account(
+    nonce: &AccountInfo,
+    recent_blockhashes: &AccountInfo,
+    rent: &AccountInfo,
+    authority: &Pubkey,
+) -> ProgramResult {
+    let instruction_accounts: [CAccountMeta; 3] =
+        [nonce.into(), recent_blockhashes.into(), rent.into()];
+
+    let instruction_data = initialize_nonce_account_data(authority);
+
+    let account_infos: [CAccountInfo; 3] = [nonce.into(), recent_blockhashes.into(), rent.into()];
+
+    _invoke(
+        &instruction_accounts,
+        &account_infos,
+        &instruction_data,
+        &[],
+    )
+}
+
+/// Change the authority of a durable nonce account.
+///
+/// # Arguments
+///
+/// * `nonce`: Nonce account.
+/// * `authority`: Current authority of the nonce account.
+/// * `new_authority`: New authority of the nonce account.
+pub fn authorize_nonce_account(
+    nonce: &AccountInfo,
+    authority: &AccountInfo,
+    new_authority: &Pubkey,
+) -> ProgramResult {
+    _authorize_nonce_account_signed(nonce, authority, new_authority, &[])
+}
+
+/// Change the authority of a durable nonce account with a program signed
+/// instruction.
+///
+/// # Arguments
+///
+/// * `nonce`: Nonce account.
+/// * `authority`: Current authority of the nonce account.
+/// * `new_authority`: New authority of the nonce account.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+pub fn authorize_nonce_account_signed<const SEEDS: usize>(
+    nonce: &AccountInfo,
+    authority: &AccountInfo,
+    new_authority: &Pubkey,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _authorize_nonce_account_signed(nonce, authority, new_authority, &signer)
+}
+
+/// Upgrade a legacy durable nonce account to the current nonce version.
+///
+/// # Arguments
+///
+/// * `nonce`: Nonce account.
+pub fn upgrade_nonce_account(nonce: &AccountInfo) -> ProgramResult {
+    let instruction_accounts: [CAccountMeta; 1] = [nonce.into()];
+
+    let instruction_data = upgrade_nonce_account_data();
+
+    let account_infos: [CAccountInfo; 1] = [nonce.into()];
+
+    _invoke(
+        &instruction_accounts,
+        &account_infos,
+        &instruction_data,
+        &[],
+    )
+}
+
+/// Create a new account and initialize it as a durable nonce account.
+///
+/// The account is allocated with the size of a nonce account and assigned to the
+/// System program before being initialized.
+///
+/// # Arguments
+///
+/// * `funder`: Funding account.
+/// * `nonce`: New nonce account.
+/// * `recent_blockhashes`: `RecentBlockhashes` sysvar account.
+/// * `rent`: `Rent` sysvar account.
+/// * `authority`: Authority of the nonce account.
+/// * `lamports`: Number of lamports to transfer to the new account.
+pub fn create_nonce_account(
+    funder: &AccountInfo,
+    nonce: &AccountInfo,
+    recent_blockhashes: &AccountInfo,
+    rent: &AccountInfo,
+    authority: &Pubkey,
+    lamports: u64,
+) -> ProgramResult {
+    _create_account_signed(
+        funder,
+        nonce,
+        lamports,
+        NonceState::size() as u64,
+        &system_program::ID,
+        &[],
+    )?;
+
+    initialize_nonce_account(nonce, recent_blockhashes, rent, authority)
+}
+
+/// Create a new account with a program signed instruction and initialize it as a
+/// durable nonce account.
+///
+/// # Arguments
+///
+/// * `funder`: Funding account.
+/// * `nonce`: New nonce account.
+/// * `recent_blockhashes`: `RecentBlockhashes` sysvar account.
+/// * `rent`: `Rent` sysvar account.
+/// * `authority`: Authority of the nonce account.
+/// * `lamports`: Number of lamports to transfer to the new account.
+/// * `signer_seeds`: Seeds used to sign the create account instruction.
+pub fn create_nonce_account_signed<const SEEDS: usize>(
+    funder: &AccountInfo,
+    nonce: &AccountInfo,
+    recent_blockhashes: &AccountInfo,
+    rent: &AccountInfo,
+    authority: &Pubkey,
+    lamports: u64,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _create_account_signed(
+        funder,
+        nonce,
+        lamports,
+        NonceState::size() as u64,
+        &system_program::ID,
+        &signer,
+    )?;
+
+    initialize_nonce_account(nonce, recent_blockhashes, rent, authority)
+}
+
+//-- Instruction builders
+
+/// Returns the metas of a `CreateAccount` instruction.
+///
+/// # Arguments
+///
+/// * `funder`: Address of the funding account.
+/// * `account`: Address of the new account.
+#[inline(always)]
+pub fn create_account_metas(funder: &Pubkey, account: &Pubkey) -> [CAccountMeta; 2] {
+    [
+        CAccountMeta::writable_signer(funder),
+        CAccountMeta::writable_signer(account),
+    ]
+}
+
+/// Returns the data of a `CreateAccount` instruction.
+///
+/// # Arguments
+///
+/// * `lamports`: Number of lamports to transfer to the new account.
+/// * `space`: Number of bytes of memory to allocate.
+/// * `owner`: Address of program that will own the new account.
+#[inline(always)]
+pub fn create_account_data(lamports: u64, space: u64, owner: &Pubkey) -> [u8; 52] {
+    // -   0..4: instruction discriminator
+    // -  4..12: lamports
+    // - 12..20: account space
+    // - 20..52: owner pubkey
+    let mut instruction_data = [0; 52];
+    // create account instruction has a '0' discriminator
+    instruction_data[4..12].copy_from_slice(&lamports.to_le_bytes());
+    instruction_data[12..20].copy_from_slice(&space.to_le_bytes());
+    instruction_data[20..52].copy_from_slice(owner.as_ref());
+
+    instruction_data
+}
+
+/// Returns the metas of a `Transfer` instruction.
+///
+/// # Arguments
+///
+/// * `from`: Address of the funding account.
+/// * `recipient`: Address of the recipient account.
+#[inline(always)]
+pub fn transfer_metas(from: &Pubkey, recipient: &Pubkey) -> [CAccountMeta; 2] {
+    [
+        CAccountMeta::writable_signer(from),
+        CAccountMeta::writable(recipient),
+    ]
+}
+
+/// Returns the data of a `Transfer` instruction.
+///
+/// # Arguments
+///
+/// * `amount`: Number of lamports to transfer.
+#[inline(always)]
+pub fn transfer_data(amount: u64) -> [u8; 12] {
+    // -   0..4: instruction discriminator
+    // -  4..12: lamports amount
+    let mut instruction_data = [0; 12];
+    // transfer instruction has a '2' discriminator
+    instruction_data[0] = 2;
+    instruction_data[4..12].copy_from_slice(&amount.to_le_bytes());
+
+    instruction_data
+}
+
+/// Returns the metas of an `Allocate` instruction.
+///
+/// # Arguments
+///
+/// * `account`: Address of the account to allocate.
+#[inline(always)]
+pub fn allocate_metas(account: &Pubkey) -> [CAccountMeta; 1] {
+    [CAccountMeta::writable_signer(account)]
+}
+
+/// Returns the data of an `Allocate` instruction.
+///
+/// # Arguments
+///
+/// * `space`: Number of bytes of memory to allocate.
+#[inline(always)]
+pub fn allocate_data(space: u64) -> [u8; 12] {
+    // -   0..4: instruction discriminator
+    // -  4..12: account space
+    let mut instruction_data = [0; 12];
+    // allocate instruction has a '8' discriminator
+    instruction_data[0] = 8;
+    instruction_data[4..12].copy_from_slice(&space.to_le_bytes());
+
+    instruction_data
+}
+
+/// Returns the metas of an `Assign` instruction.
+///
+/// # Arguments
+///
+/// * `account`: Address of the account to assign.
+#[inline(always)]
+pub fn assign_metas(account: &Pubkey) -> [CAccountMeta; 1] {
+    [CAccountMeta::writable_signer(account)]
+}
+
+/// Returns the data of an `Assign` instruction.
+///
+/// # Arguments
+///
+/// * `owner`: Address of program that will own the account.
+#[inline(always)]
+pub fn assign_data(owner: &Pubkey) -> [u8; 36] {
+    // -   0..4: instruction discriminator
+    // -  4..36: owner pubkey
+    let mut instruction_data = [0; 36];
+    // assign instruction has a '1' discriminator
+    instruction_data[0] = 1;
+    instruction_data[4..36].copy_from_slice(owner.as_ref());
+
+    instruction_data
+}
+
+/// Returns the metas of a `CreateAccountWithSeed` instruction and their length.
+///
+/// The base account is only included when it is not the funding account, so only
+/// the first `length` metas should be used.
+///
+/// # Arguments
+///
+/// * `funder`: Address of the funding account.
+/// * `account`: Address of the new account.
+/// * `base`: Address of the base account of the address derivation.
+#[inline(always)]
+pub fn create_account_with_seed_metas(
+    funder: &Pubkey,
+    account: &Pubkey,
+    base: &Pubkey,
+) -> ([CAccountMeta; 3], usize) {
+    let metas = [
+        CAccountMeta::writable_signer(funder),
+        CAccountMeta::writable(account),
+        CAccountMeta::readonly_signer(base),
+    ];
+
+    (metas, if base == funder { 2 } else { 3 })
+}
+
+/// Returns the data of a `CreateAccountWithSeed` instruction and its length.
+///
+/// The data is followed by unused bytes when the seed is shorter than
+/// `MAX_SEED_LEN`, so only the first `length` bytes should be used.
+///
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seed is longer than
+/// `MAX_SEED_LEN`.
+///
+/// # Arguments
+///
+/// * `base`: Base public key of the address derivation.
+/// * `seed`: Seed of the address derivation.
+/// * `lamports`: Number of lamports to transfer to the new account.
+/// * `space`: Number of bytes of memory to allocate.
+/// * `owner`: Address of program that will own the new account.
+#[inline(always)]
+pub fn create_account_with_seed_data(
+    base: &Pubkey,
+    seed: &str,
+    lamports: u64,
+    space: u64,
+    owner: &Pubkey,
+) -> Result<([u8; 44 + MAX_SEED_LEN + 48], usize), ProgramError> {
+    // -    0..4: instruction discriminator
+    // -   4..36: base pubkey
+    // -  36..44: seed length
+    // -  44..  : seed
+    // -   +0..8: lamports
+    // -  +8..16: account space
+    // - +16..48: owner pubkey
+    let mut instruction_data = [0; 44 + MAX_SEED_LEN + 48];
+    // create account with seed instruction has a '3' discriminator
+    instruction_data[0] = 3;
+    let offset = write_seed(&mut instruction_data, base, seed)?;
+    instruction_data[offset..offset + 8].copy_from_slice(&lamports.to_le_bytes());
+    instruction_data[offset + 8..offset + 16].copy_from_slice(&space.to_le_bytes());
+    instruction_data[offset + 16..offset + 48].copy_from_slice(owner.as_ref());
+
+    Ok((instruction_data, offset + 48))
+}
+
+/// Returns the metas of a `TransferWithSeed` instruction.
+///
+/// # Arguments
+///
+/// * `from`: Address of the funding account.
+/// * `base`: Address of the base account of the address derivation.
+/// * `recipient`: Address of the recipient account.
+#[inline(always)]
+pub fn transfer_with_seed_metas(
+    from: &Pubkey,
+    base: &Pubkey,
+    recipient: &Pubkey,
+) -> [CAccountMeta; 3] {
+    [
+        CAccountMeta::writable(from),
+        CAccountMeta::readonly_signer(base),
+        CAccountMeta::writable(recipient),
+    ]
+}
+
+/// Returns the data of a `TransferWithSeed` instruction and its length.
+///
+/// The data is followed by unused bytes when the seed is shorter than
+/// `MAX_SEED_LEN`, so only the first `length` bytes should be used.
+///
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seed is longer than
+/// `MAX_SEED_LEN`.
+///
+/// # Arguments
+///
+/// * `amount`: Number of lamports to transfer.
+/// * `seed`: Seed of the address derivation.
+/// * `owner`: Address of program used in the address derivation.
+#[inline(always)]
+pub fn transfer_with_seed_data(
+    amount: u64,
+    seed: &str,
+    owner: &Pubkey,
+) -> Result<([u8; 20 + MAX_SEED_LEN + 32], usize), ProgramError> {
+    if seed.len() > MAX_SEED_LEN {
+        return Err(ProgramError::MaxSeedLengthExceeded);
+    }
+
+    // -    0..4: instruction discriminator
+    // -   4..12: lamports amount
+    // -  12..20: seed length
+    // -  20..  : seed
+    // -  +0..32: owner pubkey
+    let mut instruction_data = [0; 20 + MAX_SEED_LEN + 32];
+    // transfer with seed instruction has a '11' discriminator
+    instruction_data[0] = 11;
+    instruction_data[4..12].copy_from_slice(&amount.to_le_bytes());
+    instruction_data[12..20].copy_from_slice(&(seed.len() as u64).to_le_bytes());
+    let offset = 20 + seed.len();
+    instruction_data[20..offset].copy_from_slice(seed.as_bytes());
+    instruction_data[offset..offset + 32].copy_from_slice(owner.as_ref());
+
+    Ok((instruction_data, offset + 32))
+}
+
+/// Returns the metas of an `AllocateWithSeed` instruction.
+///
+/// # Arguments
+///
+/// * `account`: Address of the account to allocate.
+/// * `base`: Address of the base account of the address derivation.
+#[inline(always)]
+pub fn allocate_with_seed_metas(account: &Pubkey, base: &Pubkey) -> [CAccountMeta; 2] {
+    [
+        CAccountMeta::writable(account),
+        CAccountMeta::readonly_signer(base),
+    ]
+}
+
+/// Returns the data of an `AllocateWithSeed` instruction and its length.
+///
+/// The data is followed by unused bytes when the seed is shorter than
+/// `MAX_SEED_LEN`, so only the first `length` bytes should be used.
+///
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seed is longer than
+/// `MAX_SEED_LEN`.
+///
+/// # Arguments
+///
+/// * `base`: Base public key of the address derivation.
+/// * `seed`: Seed of the address derivation.
+/// * `space`: Number of bytes of memory to allocate.
+/// * `owner`: Address of program that will own the account.
+#[inline(always)]
+pub fn allocate_with_seed_data(
+    base: &Pubkey,
+    seed: &str,
+    space: u64,
+    owner: &Pubkey,
+) -> Result<([u8; 44 + MAX_SEED_LEN + 40], usize), ProgramError> {
+    // -    0..4: instruction discriminator
+    // -   4..36: base pubkey
+    // -  36..44: seed length
+    // -  44..  : seed
+    // -   +0..8: account space
+    // -  +8..40: owner pubkey
+    let mut instruction_data = [0; 44 + MAX_SEED_LEN + 40];
+    // allocate with seed instruction has a '9' discriminator
+    instruction_data[0] = 9;
+    let offset = write_seed(&mut instruction_data, base, seed)?;
+    instruction_data[offset..offset + 8].copy_from_slice(&space.to_le_bytes());
+    instruction_data[offset + 8..offset + 40].copy_from_slice(owner.as_ref());
+
+    Ok((instruction_data, offset + 40))
+}
+
+/// Returns the metas of an `AssignWithSeed` instruction.
+///
+/// # Arguments
+///
+/// * `account`: Address of the account to assign.
+/// * `base`: Address of the base account of the address derivation.
+#[inline(always)]
+pub fn assign_with_seed_metas(account: &Pubkey, base: &Pubkey) -> [CAccountMeta; 2] {
+    [
+        CAccountMeta::writable(account),
+        CAccountMeta::readonly_signer(base),
+    ]
+}
+
+/// Returns the data of an `AssignWithSeed` instruction and its length.
+///
+/// The data is followed by unused bytes when the seed is shorter than
+/// `MAX_SEED_LEN`, so only the first `length` bytes should be used.
+///
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seed is longer than
+/// `MAX_SEED_LEN`.
+///
+/// # Arguments
+///
+/// * `base`: Base public key of the address derivation.
+/// * `seed`: Seed of the address derivation.
+/// * `owner`: Address of program that will own the account.
+#[inline(always)]
+pub fn assign_with_seed_data(
+    base: &Pubkey,
+    seed: &str,
+    owner: &Pubkey,
+) -> Result<([u8; 44 + MAX_SEED_LEN + 32], usize), ProgramError> {
+    // -    0..4: instruction discriminator
+    // -   4..36: base pubkey
+    // -  36..44: seed length
+    // -  44..  : seed
+    // -  +0..32: owner pubkey
+    let mut instruction_data = [0; 44 + MAX_SEED_LEN + 32];
+    // assign with seed instruction has a '10' discriminator
+    instruction_data[0] = 10;
+    let offset = write_seed(&mut instruction_data, base, seed)?;
+    instruction_data[offset..offset + 32].copy_from_slice(owner.as_ref());
+
+    Ok((instruction_data, offset + 32))
+}
+
+/// Returns the metas of an `AdvanceNonceAccount` instruction.
+///
+/// # Arguments
+///
+/// * `nonce`: Address of the nonce account.
+/// * `recent_blockhashes`: Address of the `RecentBlockhashes` sysvar.
+/// * `authority`: Address of the authority of the nonce account.
+#[inline(always)]
+pub fn advance_nonce_account_metas(
+    nonce: &Pubkey,
+    recent_blockhashes: &Pubkey,
+    authority: &Pubkey,
+) -> [CAccountMeta; 3] {
+    [
+        CAccountMeta::writable(nonce),
+        CAccountMeta::readonly(recent_blockhashes),
+        CAccountMeta::readonly_signer(authority),
+    ]
+}
+
+/// Returns the data of an `AdvanceNonceAccount` instruction.
+#[inline(always)]
+pub fn advance_nonce_account_data() -> [u8; 4] {
+    // advance nonce account instruction has a '4' discriminator
+    [4, 0, 0, 0]
+}
+
+/// Returns the metas of a `WithdrawNonceAccount` instruction.
+///
+/// # Arguments
+///
+/// * `nonce`: Address of the nonce account.
+/// * `recipient`: Address of the recipient account.
+/// * `recent_blockhashes`: Address of the `RecentBlockhashes` sysvar.
+/// * `rent`: Address of the `Rent` sysvar.
+/// * `authority`: Address of the authority of the nonce account.
+#[inline(always)]
+pub fn withdraw_nonce_account_metas(
+    nonce: &Pubkey,
+    recipient: &Pubkey,
+    recent_blockhashes: &Pubkey,
+    rent: &Pubkey,
+    authority: &Pubkey,
+) -> [CAccountMeta; 5] {
+    [
+        CAccountMeta::writable(nonce),
+        CAccountMeta::writable(recipient),
+        CAccountMeta::readonly(recent_blockhashes),
+        CAccountMeta::readonly(rent),
+        CAccountMeta::readonly_signer(authority),
+    ]
+}
+
+/// Returns the data of a `WithdrawNonceAccount` instruction.
+///
+/// # Arguments
+///
+/// * `lamports`: Number of lamports to withdraw.
+#[inline(always)]
+pub fn withdraw_nonce_account_data(lamports: u64) -> [u8; 12] {
+    // -   0..4: instruction discriminator
+    // -  4..12: lamports
+    let mut instruction_data = [0; 12];
+    // withdraw nonce account instruction has a '5' discriminator
+    instruction_data[0] = 5;
+    instruction_data[4..12].copy_from_slice(&lamports.to_le_bytes());
+
+    instruction_data
+}
+
+/// Returns the metas of an `InitializeNonceAccount` instruction.
+///
+/// # Arguments
+///
+/// * `nonce`: Address of the nonce account.
+/// * `recent_blockhashes`: Address of the `RecentBlockhashes` sysvar.
+/// * `rent`: Address of the `Rent` sysvar.
+#[inline(always)]
+pub fn initialize_nonce_account_metas(
+    nonce: &Pubkey,
+    recent_blockhashes: &Pubkey,
+    rent: &Pubkey,
+) -> [CAccountMeta; 3] {
+    [
+        CAccountMeta::writable(nonce),
+        CAccountMeta::readonly(recent_blockhashes),
+        CAccountMeta::readonly(rent),
+    ]
+}
+
+/// Returns the data of an `InitializeNonceAccount` instruction.
+///
+/// # Arguments
+///
+/// * `authority`: Authority of the nonce account.
+#[inline(always)]
+pub fn initialize_nonce_account_data(authority: &Pubkey) -> [u8; 36] {
+    // -   0..4: instruction discriminator
+    // -  4..36: authority pubkey
+    let mut instruction_data = [0; 36];
+    // initialize nonce account instruction has a '6' discriminator
+    instruction_data[0] = 6;
+    instruction_data[4..36].copy_from_slice(authority.as_ref());
+
+    instruction_data
+}
+
+/// Returns the metas of an `AuthorizeNonceAccount` instruction.
+///
+/// # Arguments
+///
+/// * `nonce`: Address of the nonce account.
+/// * `authority`: Address of the current authority of the nonce account.
+#[inline(always)]
+pub fn authorize_nonce_account_metas(nonce: &Pubkey, authority: &Pubkey) -> [CAccountMeta; 2] {
+    [
+        CAccountMeta::writable(nonce),
+        CAccountMeta::readonly_signer(authority),
+    ]
+}
+
+/// Returns the data of an `AuthorizeNonceAccount` instruction.
+///
+/// # Arguments
+///
+/// * `new_authority`: New authority of the nonce account.
+#[inline(always)]
+pub fn authorize_nonce_account_data(new_authority: &Pubkey) -> [u8; 36] {
+    // -   0..4: instruction discriminator
+    // -  4..36: new authority pubkey
+    let mut instruction_data = [0; 36];
+    // authorize nonce account instruction has a '7' discriminator
+    instruction_data[0] = 7;
+    instruction_data[4..36].copy_from_slice(new_authority.as_ref());
+
+    instruction_data
+}
+
+/// Returns the metas of an `UpgradeNonceAccount` instruction.
+///
+/// # Arguments
+///
+/// * `nonce`: Address of the nonce account.
+#[inline(always)]
+pub fn upgrade_nonce_account_metas(nonce: &Pubkey) -> [CAccountMeta; 1] {
+    [CAccountMeta::writable(nonce)]
+}
+
+/// Returns the data of an `UpgradeNonceAccount` instruction.
+#[inline(always)]
+pub fn upgrade_nonce_account_data() -> [u8; 4] {
+    // upgrade nonce account instruction has a '12' discriminator
+    [12, 0, 0, 0]
+}
+
+//-- Internal functions
+
+/// Create a new account or initialize an account that already holds lamports
+/// either with or without a program signed instruction.
+fn _create_or_initialize_signed(
+    funder: &AccountInfo,
+    account: &AccountInfo,
+    space: u64,
+    owner: &Pubkey,
+    signer: &[CSigner],
+) -> ProgramResult {
+    let minimum_balance = Rent::get()?.minimum_balance(space as usize);
+    let lamports = *account.try_borrow_lamports()?;
+
+    if lamports == 0 {
+        return _create_account_signed(funder, account, minimum_balance, space, owner, signer);
+    }
+
+    if minimum_balance > lamports {
+        _transfer_signed(funder, account, minimum_balance - lamports, signer)?;
+    }
+
+    _allocate_signed(account, space, signer)?;
+    _assign_signed(account, owner, signer)
+}
+
+/// Transfer lamports between accounts either with or without a program signed
+/// instruction.
+fn _transfer_signed(
+    from: &AccountInfo,
+    recipient: &AccountInfo,
+    amount: u64,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // funding account is always a signer
+    let instruction_accounts: [CAccountMeta; 2] =
+        [(from, from.is_writable(), true).into(), recipient.into()];
+
+    let instruction_data = transfer_data(amount);
+
+    let account_infos: [CAccountInfo; 2] = [from.into(), recipient.into()];
+
+    _invoke(
+        &instruction_accounts,
+        &account_infos,
+        &instruction_data,
+        signer,
+    )
+}
+
+/// Transfer all lamports of an account either with or without a program signed
+/// instruction.
+fn _transfer_all_signed(
+    from: &AccountInfo,
+    recipient: &AccountInfo,
+    rent_exempt: bool,
+    signer: &[CSigner],
+) -> ProgramResult {
+    let floor = if rent_exempt {
+        Rent::get()?.minimum_balance(from.data_len())
+    } else {
+        0
+    };
+
+    let amount = from.try_borrow_lamports()?.saturating_sub(floor);
+
+    if amount == 0 {
+        return Ok(());
+    }
+
+    if from.owner() == &system_program::ID {
+        _transfer_signed(from, recipient, amount, signer)
+    } else {
+        let mut lamports = from.try_borrow_mut_lamports()?;
+        let mut recipient_lamports = recipient.try_borrow_mut_lamports()?;
+
+        *recipient_lamports = recipient_lamports
+            .checked_add(amount)
+            .ok_or(ProgramError::ArithmeticOverflow)?;
+        *lamports -= amount;
+
+        Ok(())
+    }
+}
+
+/// Create a new account at an address derived from a base public key and a seed
+/// either with or without a program signed instruction.
+#[allow(clippy::too_many_arguments)]
+fn _create_account_with_seed_signed(
+    funder: &AccountInfo,
+    account: &AccountInfo,
+    base: &AccountInfo,
+    seed: &str,
+    lamports: u64,
+    space: u64,
+    owner: &Pubkey,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // base account is always a signer
+    let instruction_accounts: [CAccountMeta; 3] = [
+        funder.into(),
+        account.into(),
+        (base, base.is_writable(), true).into(),
+    ];
+
+    // the base account is only included when it is not the funder
+    let accounts_len = if base.key() == funder.key() { 2 } else { 3 };
+
+    let (instruction_data, data_len) =
+        create_account_with_seed_data(base.key(), seed, lamports, space, owner)?;
+
+    let account_infos: [CAccountInfo; 3] = [funder.into(), account.into(), base.into()];
+
+    _invoke(
+        &instruction_accounts[..accounts_len],
+        &account_infos[..accounts_len],
+        &instruction_data[..data_len],
+        signer,
+    )
+}
+
+/// Transfer lamports from an account at an address derived from a base public key
+/// and a seed either with or without a program signed instruction.
+fn _transfer_with_seed_signed(
+    from: &AccountInfo,
+    base: &AccountInfo,
+    recipient: &AccountInfo,
+    amount: u64,
+    seed: &str,
+    owner: &Pubkey,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // base account is always a signer
+    let instruction_accounts: [CAccountMeta; 3] = [
+        from.into(),
+        (base, base.is_writable(), true).into(),
+        recipient.into(),
+    ];
+
+    let (instruction_data, data_len) = transfer_with_seed_data(amount, seed, owner)?;
+
+    let account_infos: [CAccountInfo; 3] = [from.into(), base.into(), recipient.into()];
+
+    _invoke(
+        &instruction_accounts,
+        &account_infos,
+        &instruction_data[..data_len],
+        signer,
+    )
+}
+
+/// Allocate space for an account either with or without a program signed
+/// instruction.
+fn _allocate_signed(account: &AccountInfo, space: u64, signer: &[CSigner]) -> ProgramResult {
+    // account being allocated is always a signer
+    let instruction_accounts: [CAccountMeta; 1] = [(account, account.is_writable(), true).into()];
+
+    let instruction_data = allocate_data(space);
+
+    let account_infos: [CAccountInfo; 1] = [account.into()];
+
+    _invoke(
+        &instruction_accounts,
+        &account_infos,
+        &instruction_data,
+        signer,
+    )
+}
+
+/// Assign an account to a program either with or without a program signed
+/// instruction.
+fn _assign_signed(account: &AccountInfo, owner: &Pubkey, signer: &[CSigner]) -> ProgramResult {
+    // account being assigned is always a signer
+    let instruction_accounts: [CAccountMeta; 1] = [(account, account.is_writable(), true).into()];
+
+    let instruction_data = assign_data(owner);
+
+    let account_infos: [CAccountInfo; 1] = [account.into()];
+
+    _invoke(
+        &instruction_accounts,
+        &account_infos,
+        &instruction_data,
+        signer,
+    )
+}
+
+/// Allocate space for an account at an address derived from a base public key and
+/// a seed either with or without a program signed instruction.
+fn _allocate_with_seed_signed(
+    account: &AccountInfo,
+    base: &AccountInfo,
+    seed: &str,
+    space: u64,
+    owner: &Pubkey,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // base account is always a signer
+    let instruction_accounts: [CAccountMeta; 2] =
+        [account.into(), (base, base.is_writable(), true).into()];
+
+    let (instruction_data, data_len) = allocate_with_seed_data(base.key(), seed, space, owner)?;
+
+    let account_infos: [CAccountInfo; 2] = [account.into(), base.into()];
+
+    _invoke(
+        &instruction_accounts,
+        &account_infos,
+        &instruction_data[..data_len],
+        signer,
+    )
+}
+
+/// Assign an account at an address derived from a base public key and a seed to a
+/// program either with or without a program signed instruction.
+fn _assign_with_seed_signed(
+    account: &AccountInfo,
+    base: &AccountInfo,
+    seed: &str,
+    owner: &Pubkey,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // base account is always a signer
+    let instruction_accounts: [CAccountMeta; 2] =
+        [account.into(), (base, base.is_writable(), true).into()];
+
+    let (instruction_data, data_len) = assign_with_seed_data(base.key(), seed, owner)?;
+
+    let account_infos: [CAccountInfo; 2] = [account.into(), base.into()];
+
+    _invoke(
+        &instruction_accounts,
+        &account_infos,
+        &instruction_data[..data_len],
+        signer,
+    )
+}
+
+/// Advance the stored blockhash of a durable nonce account either with or without a
+/// program signed instruction.
+fn _advance_nonce_account_signed(
+    nonce: &AccountInfo,
+    recent_blockhashes: &AccountInfo,
+    authority: &AccountInfo,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // nonce authority is always a signer
+    let instruction_accounts: [CAccountMeta; 3] = [
+        nonce.into(),
+        recent_blockhashes.into(),
+        (authority, authority.is_writable(), true).into(),
+    ];
+
+    let instruction_data = advance_nonce_account_data();
+
+    let account_infos: [CAccountInfo; 3] =
+        [nonce.into(), recent_blockhashes.into(), authority.into()];
+
+    _invoke(
+        &instruction_accounts,
+        &account_infos,
+        &instruction_data,
+        signer,
+    )
+}
+
+/// Withdraw lamports from a durable nonce account either with or without a program
+/// signed instruction.
+fn _withdraw_nonce_account_signed(
+    nonce: &AccountInfo,
+    recipient: &AccountInfo,
+    recent_blockhashes: &AccountInfo,
+    rent: &AccountInfo,
+    authority: &AccountInfo,
+    lamports: u64,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // nonce authority is always a signer
+    let instruction_accounts: [CAccountMeta; 5] = [
+        nonce.into(),
+        recipient.into(),
+        recent_blockhashes.into(),
+        rent.into(),
+        (authority, authority.is_writable(), true).into(),
+    ];
+
+    let instruction_data = withdraw_nonce_account_data(lamports);
+
+    let account_infos: [CAccountInfo; 5] = [
+        nonce.into(),
+        recipient.into(),
+        recent_blockhashes.into(),
+        rent.into(),
+        authority.into(),
+    ];
+
+    _invoke(
+        &instruction_accounts,
+        &account_infos,
+        &instruction_data,
+        signer,
+    )
+}
+
+/// Change the authority of a durable nonce account either with or without a
+/// program signed instruction.
+fn _authorize_nonce_account_signed(
+    nonce: &AccountInfo,
+    authority: &AccountInfo,
+    new_authority: &Pubkey,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // nonce authority is always a signer
+    let instruction_accounts: [CAccountMeta; 2] = [
+        nonce.into(),
+        (authority, authority.is_writable(), true).into(),
+    ];
+
+    let instruction_data = authorize_nonce_account_data(new_authority);
+
+    let account_infos: [CAccountInfo; 2] = [nonce.into(), authority.into()];
+
+    _invoke(
+        &instruction_accounts,
+        &account_infos,
+        &instruction_data,
+        signer,
+    )
+}
+
+/// Writes the base pubkey and seed of a seeded instruction after the discriminator,
+/// returning the offset of the next field.
+///
+/// Returns `ProgramError::MaxSeedLengthExceeded` if the seed is longer than
+/// `MAX_SEED_LEN`.
+fn write_seed(
+    instruction_data: &mut [u8],
+    base: &Pubkey,
+    seed: &str,
+) -> Result<usize, ProgramError> {
+    if seed.len() > MAX_SEED_LEN {
+        return Err(ProgramError::MaxSeedLengthExceeded);
+    }
+
+    // -   4..36: base pubkey
+    // -  36..44: seed length
+    // -  44..  : seed
+    instruction_data[4..36].copy_from_slice(base.as_ref());
+    instruction_data[36..44].copy_from_slice(&(seed.len() as u64).to_le_bytes());
+    instruction_data[44..44 + seed.len()].copy_from_slice(seed.as_bytes());
+
+    Ok(44 + seed.len())
+}
+
+/// Invoke a System program instruction.
+#[inline(always)]
+fn _invoke(
+    instruction_accounts: &[CAccountMeta],
+    account_infos: &[CAccountInfo],
+    instruction_data: &[u8],
+    signer: &[CSigner],
+) -> ProgramResult {
+    let instruction = CInstruction {
+        program_id: &system_program::ID,
+        accounts: instruction_accounts.as_ptr(),
+        accounts_len: instruction_accounts.len() as u64,
+        data: instruction_data.as_ptr(),
+        data_len: instruction_data.len() as u64,
+    };
+
+    cpi::invoke_signed_c(&instruction, account_infos, signer)
+}
+
+/// Create a new account.
+///
+/// This function is used to create a new account either with or without a program
+/// signed instruction.
+///
+/// # Arguments
+///
+/// * `funder`: Funding account.
+/// * `account`: New account.
+/// * `lamports`: Number of lamports to transfer to the new account.
+/// * `space`: Number of bytes of memory to allocate.
+/// * `owner`: Address of program that will own the new account.
+/// * `signer`: Seeds used to sign the instruction.
+fn _create_account_signed(
+    funder: &AccountInfo,
+    account: &AccountInfo,
+    lamports: u64,
+    space: u64,
+    owner: &Pubkey,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // account being created is always a signer
+    let instruction_accounts = (funder, AsSigner(account)).into_account_metas();
+
+    let instruction_data = create_account_data(lamports, space, owner);
+
+    // account infos
+    let account_infos: [CAccountInfo; 2] = [funder.into(), account.into()];
 
-    // keep clippy happy
-    #[cfg(not(target_os = "solana"))]
-    core::hint::black_box(&(&instruction, &account_infos, &signer));
+    _invoke(
+        &instruction_accounts,
+        &account_infos,
+        &instruction_data,
+        signer,
+    )
 }
@@ -0,0 +1,79 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Per-instruction sysvar cache.
+//!
+//! Programs cannot use writable static variables, so the cache is a value created
+//! at the start of the instruction (e.g., in the instruction processor) and passed
+//! to the code paths that consult the sysvars.
+
+use std::cell::OnceCell;
+
+use solana_program::{clock::Clock, program_error::ProgramError, rent::Rent, sysvar::Sysvar};
+
+/// Lazily initialized cache of the `Clock` and `Rent` sysvars.
+///
+/// Each sysvar is fetched through its syscall the first time it is accessed; later
+/// accesses return the cached value.
+///
+/// # Examples
+///
+/// ```ignore
+/// let sysvars = SysvarCache::new();
+///
+/// let timestamp = sysvars.clock()?.unix_timestamp;
+/// let minimum_balance = sysvars.rent()?.minimum_balance(space);
+/// ```
+#[derive(Default)]
+pub struct SysvarCache {
+    /// Cached `Clock` sysvar.
+    clock: OnceCell<Clock>,
+
+    /// Cached `Rent` sysvar.
+    rent: OnceCell<Rent>,
+}
+
+impl SysvarCache {
+    /// Creates an empty cache.
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            clock: OnceCell::new(),
+            rent: OnceCell::new(),
+        }
+    }
+
+    /// Returns the `Clock` sysvar.
+    #[inline(always)]
+    pub fn clock(&self) -> Result<&Clock, ProgramError> {
+        get_or_fetch(&self.clock)
+    }
+
+    /// Returns the `Rent` sysvar.
+    #[inline(always)]
+    pub fn rent(&self) -> Result<&Rent, ProgramError> {
+        get_or_fetch(&self.rent)
+    }
+}
+
+/// Returns the cached sysvar, fetching it on first access.
+#[inline(always)]
+fn get_or_fetch<T: Sysvar>(cell: &OnceCell<T>) -> Result<&T, ProgramError> {
+    if let Some(sysvar) = cell.get() {
+        return Ok(sysvar);
+    }
+
+    let sysvar = T::get()?;
+    Ok(cell.get_or_init(|| sysvar))
+}
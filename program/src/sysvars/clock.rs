@@ -0,0 +1,67 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Clock sysvar access.
+//!
+//! The clock sysvar is read through the `sol_get_clock_sysvar` syscall, so the
+//! sysvar account does not need to be passed to the instruction.
+
+use solana_program::{
+    clock::{Epoch, Slot, UnixTimestamp},
+    program_error::ProgramError,
+};
+
+pub use solana_program::{clock::Clock, sysvar::clock::ID};
+
+/// Returns the `Clock` sysvar.
+///
+/// Returns the error of the syscall if the sysvar cannot be read.
+#[inline(always)]
+pub fn get() -> Result<Clock, ProgramError> {
+    let mut clock = std::mem::MaybeUninit::<Clock>::uninit();
+
+    #[cfg(target_os = "solana")]
+    let result =
+        unsafe { solana_program::syscalls::sol_get_clock_sysvar(clock.as_mut_ptr() as *mut u8) };
+
+    // keep clippy happy
+    #[cfg(not(target_os = "solana"))]
+    let result = {
+        core::hint::black_box(&mut clock);
+        u64::from(ProgramError::UnsupportedSysvar)
+    };
+
+    match result {
+        solana_program::entrypoint::SUCCESS => Ok(unsafe { clock.assume_init() }),
+        error => Err(ProgramError::from(error)),
+    }
+}
+
+/// Returns the current slot.
+#[inline(always)]
+pub fn slot() -> Result<Slot, ProgramError> {
+    get().map(|clock| clock.slot)
+}
+
+/// Returns the current epoch.
+#[inline(always)]
+pub fn epoch() -> Result<Epoch, ProgramError> {
+    get().map(|clock| clock.epoch)
+}
+
+/// Returns the estimated current unix timestamp.
+#[inline(always)]
+pub fn unix_timestamp() -> Result<UnixTimestamp, ProgramError> {
+    get().map(|clock| clock.unix_timestamp)
+}
@@ -0,0 +1,85 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Epoch rewards sysvar access.
+//!
+//! The epoch rewards sysvar is read through the `sol_get_epoch_rewards_sysvar`
+//! syscall, so the sysvar account does not need to be passed to the instruction.
+
+use solana_program::{hash::Hash, program_error::ProgramError};
+
+pub use solana_program::sysvar::epoch_rewards::ID;
+
+/// Epoch rewards sysvar.
+///
+/// This mirrors the layout of the sysvar used by the runtime with partitioned
+/// epoch rewards, which differs from the `EpochRewards` type of older
+/// `solana-program` versions.
+#[repr(C, align(16))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EpochRewards {
+    /// Block height of the first block of the rewards distribution.
+    pub distribution_starting_block_height: u64,
+
+    /// Number of partitions of the rewards distribution.
+    pub num_partitions: u64,
+
+    /// Blockhash of the parent block of the first block of the distribution.
+    pub parent_blockhash: Hash,
+
+    /// Total points of the stake accounts of the epoch.
+    pub total_points: u128,
+
+    /// Total rewards of the epoch, in lamports.
+    pub total_rewards: u64,
+
+    /// Rewards distributed so far, in lamports.
+    pub distributed_rewards: u64,
+
+    /// Indicates whether the rewards distribution is in progress.
+    pub active: bool,
+}
+
+/// Returns the `EpochRewards` sysvar.
+///
+/// Returns the error of the syscall if the sysvar cannot be read.
+#[inline(always)]
+pub fn get() -> Result<EpochRewards, ProgramError> {
+    let mut epoch_rewards = std::mem::MaybeUninit::<EpochRewards>::uninit();
+
+    #[cfg(target_os = "solana")]
+    let result = unsafe {
+        solana_program::syscalls::sol_get_epoch_rewards_sysvar(epoch_rewards.as_mut_ptr() as *mut u8)
+    };
+
+    // keep clippy happy
+    #[cfg(not(target_os = "solana"))]
+    let result = {
+        core::hint::black_box(&mut epoch_rewards);
+        u64::from(ProgramError::UnsupportedSysvar)
+    };
+
+    match result {
+        solana_program::entrypoint::SUCCESS => Ok(unsafe { epoch_rewards.assume_init() }),
+        error => Err(ProgramError::from(error)),
+    }
+}
+
+/// Indicates whether the rewards distribution period is active.
+///
+/// Stake accounts cannot be modified while the rewards are being distributed.
+#[inline(always)]
+pub fn is_active() -> Result<bool, ProgramError> {
+    get().map(|epoch_rewards| epoch_rewards.active)
+}
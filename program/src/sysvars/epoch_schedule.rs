@@ -0,0 +1,92 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Epoch schedule sysvar access.
+//!
+//! The epoch schedule sysvar is read through the `sol_get_epoch_schedule_sysvar`
+//! syscall, so the sysvar account does not need to be passed to the instruction.
+
+use solana_program::{
+    clock::{Epoch, Slot},
+    program_error::ProgramError,
+};
+
+pub use solana_program::{epoch_schedule::EpochSchedule, sysvar::epoch_schedule::ID};
+
+/// Returns the `EpochSchedule` sysvar.
+///
+/// Returns the error of the syscall if the sysvar cannot be read.
+#[inline(always)]
+pub fn get() -> Result<EpochSchedule, ProgramError> {
+    let mut epoch_schedule = std::mem::MaybeUninit::<EpochSchedule>::uninit();
+
+    #[cfg(target_os = "solana")]
+    let result = unsafe {
+        solana_program::syscalls::sol_get_epoch_schedule_sysvar(
+            epoch_schedule.as_mut_ptr() as *mut u8
+        )
+    };
+
+    // keep clippy happy
+    #[cfg(not(target_os = "solana"))]
+    let result = {
+        core::hint::black_box(&mut epoch_schedule);
+        u64::from(ProgramError::UnsupportedSysvar)
+    };
+
+    match result {
+        solana_program::entrypoint::SUCCESS => Ok(unsafe { epoch_schedule.assume_init() }),
+        error => Err(ProgramError::from(error)),
+    }
+}
+
+/// Returns the epoch of the given slot.
+///
+/// # Arguments
+///
+/// * `slot`: Slot to look up.
+#[inline(always)]
+pub fn get_epoch_for_slot(slot: Slot) -> Result<Epoch, ProgramError> {
+    get().map(|epoch_schedule| epoch_schedule.get_epoch(slot))
+}
+
+/// Returns the first slot of the given epoch.
+///
+/// # Arguments
+///
+/// * `epoch`: Epoch to look up.
+#[inline(always)]
+pub fn get_first_slot_in_epoch(epoch: Epoch) -> Result<Slot, ProgramError> {
+    get().map(|epoch_schedule| epoch_schedule.get_first_slot_in_epoch(epoch))
+}
+
+/// Returns the last slot of the given epoch.
+///
+/// # Arguments
+///
+/// * `epoch`: Epoch to look up.
+#[inline(always)]
+pub fn get_last_slot_in_epoch(epoch: Epoch) -> Result<Slot, ProgramError> {
+    get().map(|epoch_schedule| epoch_schedule.get_last_slot_in_epoch(epoch))
+}
+
+/// Returns the number of slots of the given epoch.
+///
+/// # Arguments
+///
+/// * `epoch`: Epoch to look up.
+#[inline(always)]
+pub fn get_slots_in_epoch(epoch: Epoch) -> Result<u64, ProgramError> {
+    get().map(|epoch_schedule| epoch_schedule.get_slots_in_epoch(epoch))
+}
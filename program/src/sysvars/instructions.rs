@@ -0,0 +1,225 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Instructions sysvar access.
+//!
+//! The instructions sysvar contains the serialized instructions of the current
+//! transaction:
+//!
+//! * `u16` number of instructions
+//! * `u16` offset of each instruction
+//! * instructions, where each instruction is serialized as:
+//!   - `u16` number of accounts
+//!   - account metas, each as a `u8` flags (signer and writable bits) and a
+//!     32-byte public key
+//!   - 32-byte program id
+//!   - `u16` length of the instruction data
+//!   - instruction data
+//! * `u16` index of the currently executing instruction
+
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{AccountInfo, Ref};
+
+pub use solana_program::sysvar::instructions::ID;
+
+/// Size of a serialized account meta (flags and public key).
+const ACCOUNT_META_LEN: usize = 1 + std::mem::size_of::<Pubkey>();
+
+/// Flag of a signer account meta.
+const IS_SIGNER: u8 = 0b0000_0001;
+
+/// Flag of a writable account meta.
+const IS_WRITABLE: u8 = 0b0000_0010;
+
+/// Zero-copy view over the instructions sysvar account data.
+pub struct Instructions<'a> {
+    /// Data of the sysvar account.
+    data: Ref<'a, [u8]>,
+}
+
+impl<'a> Instructions<'a> {
+    /// Creates a view over the instructions sysvar account.
+    ///
+    /// Returns `ProgramError::UnsupportedSysvar` if the account is not the
+    /// instructions sysvar.
+    pub fn try_from(account: &'a AccountInfo) -> Result<Self, ProgramError> {
+        if account.key() != &ID {
+            return Err(ProgramError::UnsupportedSysvar);
+        }
+
+        Ok(Self {
+            data: account.try_borrow_data()?,
+        })
+    }
+
+    /// Returns the number of instructions in the transaction.
+    #[inline(always)]
+    pub fn num_instructions(&self) -> usize {
+        read_u16(&self.data, 0) as usize
+    }
+
+    /// Returns the index of the currently executing instruction.
+    #[inline(always)]
+    pub fn current_index(&self) -> usize {
+        read_u16(&self.data, self.data.len() - std::mem::size_of::<u16>()) as usize
+    }
+
+    /// Returns the instruction at the given index.
+    ///
+    /// Returns `ProgramError::InvalidArgument` if the index is out of bounds.
+    pub fn load_instruction_at(
+        &self,
+        index: usize,
+    ) -> Result<IntrospectedInstruction<'_>, ProgramError> {
+        if index >= self.num_instructions() {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Ok(self.instruction_at(index))
+    }
+
+    /// Returns the currently executing instruction.
+    #[inline(always)]
+    pub fn load_current_instruction(&self) -> Result<IntrospectedInstruction<'_>, ProgramError> {
+        self.load_instruction_at(self.current_index())
+    }
+
+    /// Returns the instruction at the given offset from the currently executing
+    /// instruction (e.g., `-1` for the preceding instruction).
+    ///
+    /// Returns `ProgramError::InvalidArgument` if the resulting index is out of
+    /// bounds.
+    pub fn load_relative_instruction(
+        &self,
+        offset: isize,
+    ) -> Result<IntrospectedInstruction<'_>, ProgramError> {
+        let index = self
+            .current_index()
+            .checked_add_signed(offset)
+            .ok_or(ProgramError::InvalidArgument)?;
+
+        self.load_instruction_at(index)
+    }
+
+    /// Returns an iterator over the instructions of the transaction.
+    #[inline(always)]
+    pub fn iter(&self) -> impl Iterator<Item = IntrospectedInstruction<'_>> {
+        (0..self.num_instructions()).map(|index| self.instruction_at(index))
+    }
+
+    /// Returns the instruction at the given (valid) index.
+    #[inline(always)]
+    fn instruction_at(&self, index: usize) -> IntrospectedInstruction<'_> {
+        let offset = read_u16(&self.data, std::mem::size_of::<u16>() * (index + 1)) as usize;
+
+        IntrospectedInstruction {
+            raw: &self.data[offset..],
+        }
+    }
+}
+
+/// Zero-copy view over an instruction of the instructions sysvar.
+pub struct IntrospectedInstruction<'a> {
+    /// Serialized instruction (up to the end of the sysvar data).
+    raw: &'a [u8],
+}
+
+impl<'a> IntrospectedInstruction<'a> {
+    /// Returns the number of accounts of the instruction.
+    #[inline(always)]
+    pub fn num_accounts(&self) -> usize {
+        read_u16(self.raw, 0) as usize
+    }
+
+    /// Returns the account meta at the given index.
+    ///
+    /// Returns `None` if the index is out of bounds.
+    #[inline(always)]
+    pub fn account_meta(&self, index: usize) -> Option<IntrospectedAccountMeta<'a>> {
+        (index < self.num_accounts()).then(|| self.account_meta_at(index))
+    }
+
+    /// Returns an iterator over the account metas of the instruction.
+    #[inline(always)]
+    pub fn account_metas(&self) -> impl Iterator<Item = IntrospectedAccountMeta<'a>> + '_ {
+        (0..self.num_accounts()).map(|index| self.account_meta_at(index))
+    }
+
+    /// Returns the program id of the instruction.
+    #[inline(always)]
+    pub fn program_id(&self) -> &'a Pubkey {
+        let offset = self.program_id_offset();
+        unsafe { &*(self.raw[offset..offset + 32].as_ptr() as *const Pubkey) }
+    }
+
+    /// Returns the data of the instruction.
+    #[inline(always)]
+    pub fn data(&self) -> &'a [u8] {
+        let offset = self.program_id_offset() + std::mem::size_of::<Pubkey>();
+        let len = read_u16(self.raw, offset) as usize;
+        let offset = offset + std::mem::size_of::<u16>();
+
+        &self.raw[offset..offset + len]
+    }
+
+    /// Returns the account meta at the given (valid) index.
+    #[inline(always)]
+    fn account_meta_at(&self, index: usize) -> IntrospectedAccountMeta<'a> {
+        let offset = std::mem::size_of::<u16>() + index * ACCOUNT_META_LEN;
+
+        IntrospectedAccountMeta {
+            raw: &self.raw[offset..offset + ACCOUNT_META_LEN],
+        }
+    }
+
+    /// Returns the offset of the program id in the serialized instruction.
+    #[inline(always)]
+    fn program_id_offset(&self) -> usize {
+        std::mem::size_of::<u16>() + self.num_accounts() * ACCOUNT_META_LEN
+    }
+}
+
+/// Zero-copy view over an account meta of an instruction of the instructions
+/// sysvar.
+pub struct IntrospectedAccountMeta<'a> {
+    /// Serialized account meta (flags and public key).
+    raw: &'a [u8],
+}
+
+impl<'a> IntrospectedAccountMeta<'a> {
+    /// Returns the public key of the account.
+    #[inline(always)]
+    pub fn key(&self) -> &'a Pubkey {
+        unsafe { &*(self.raw[1..ACCOUNT_META_LEN].as_ptr() as *const Pubkey) }
+    }
+
+    /// Indicates whether the account is a signer of the instruction.
+    #[inline(always)]
+    pub fn is_signer(&self) -> bool {
+        self.raw[0] & IS_SIGNER != 0
+    }
+
+    /// Indicates whether the account is writable in the instruction.
+    #[inline(always)]
+    pub fn is_writable(&self) -> bool {
+        self.raw[0] & IS_WRITABLE != 0
+    }
+}
+
+/// Reads a little-endian `u16` at the given offset.
+#[inline(always)]
+fn read_u16(data: &[u8], offset: usize) -> u16 {
+    u16::from_le_bytes([data[offset], data[offset + 1]])
+}
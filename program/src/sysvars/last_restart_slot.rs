@@ -0,0 +1,63 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Last restart slot sysvar access.
+//!
+//! The last restart slot sysvar is read through the `sol_get_last_restart_slot`
+//! syscall, so the sysvar account does not need to be passed to the instruction.
+
+use solana_program::{clock::Slot, program_error::ProgramError};
+
+use super::clock;
+
+pub use solana_program::{last_restart_slot::LastRestartSlot, sysvar::last_restart_slot::ID};
+
+/// Returns the `LastRestartSlot` sysvar.
+///
+/// Returns the error of the syscall if the sysvar cannot be read.
+#[inline(always)]
+pub fn get() -> Result<LastRestartSlot, ProgramError> {
+    let mut last_restart_slot = std::mem::MaybeUninit::<LastRestartSlot>::uninit();
+
+    #[cfg(target_os = "solana")]
+    let result = unsafe {
+        solana_program::syscalls::sol_get_last_restart_slot(
+            last_restart_slot.as_mut_ptr() as *mut u8
+        )
+    };
+
+    // keep clippy happy
+    #[cfg(not(target_os = "solana"))]
+    let result = {
+        core::hint::black_box(&mut last_restart_slot);
+        u64::from(ProgramError::UnsupportedSysvar)
+    };
+
+    match result {
+        solana_program::entrypoint::SUCCESS => Ok(unsafe { last_restart_slot.assume_init() }),
+        error => Err(ProgramError::from(error)),
+    }
+}
+
+/// Returns the slot of the last cluster restart.
+#[inline(always)]
+pub fn last_restart_slot() -> Result<Slot, ProgramError> {
+    get().map(|sysvar| sysvar.last_restart_slot)
+}
+
+/// Returns the number of slots since the last cluster restart.
+#[inline(always)]
+pub fn slots_since_restart() -> Result<u64, ProgramError> {
+    Ok(clock::slot()?.saturating_sub(last_restart_slot()?))
+}
@@ -0,0 +1,82 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Zero-copy sysvar helpers.
+
+pub mod cache;
+pub mod clock;
+pub mod epoch_rewards;
+pub mod epoch_schedule;
+pub mod instructions;
+pub mod last_restart_slot;
+pub mod slot_hashes;
+pub mod slot_history;
+pub mod stake_history;
+
+use solana_program::{entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey};
+
+/// Result of `sol_get_sysvar` when the range exceeds the sysvar data.
+const OFFSET_LENGTH_EXCEEDS_SYSVAR: u64 = 1;
+
+/// Result of `sol_get_sysvar` when the sysvar is not available.
+const SYSVAR_NOT_FOUND: u64 = 2;
+
+#[cfg(target_os = "solana")]
+extern "C" {
+    fn sol_get_sysvar(
+        sysvar_id_addr: *const u8,
+        var_addr: *mut u8,
+        offset: u64,
+        length: u64,
+    ) -> u64;
+}
+
+/// Reads a range of the data of a sysvar into the buffer.
+///
+/// This gives access to any sysvar, including sysvars without typed support, and
+/// only copies the requested range of the data.
+///
+/// Returns `ProgramError::InvalidArgument` if the range exceeds the sysvar data
+/// and `ProgramError::UnsupportedSysvar` if the sysvar is not available.
+///
+/// # Arguments
+///
+/// * `sysvar_id`: Address of the sysvar.
+/// * `offset`: Offset of the range in the sysvar data.
+/// * `dst`: Buffer to read the range into; its length is the length of the range.
+#[inline(always)]
+pub fn get_into(sysvar_id: &Pubkey, offset: usize, dst: &mut [u8]) -> ProgramResult {
+    #[cfg(target_os = "solana")]
+    let result = unsafe {
+        sol_get_sysvar(
+            sysvar_id.as_ref().as_ptr(),
+            dst.as_mut_ptr(),
+            offset as u64,
+            dst.len() as u64,
+        )
+    };
+
+    // keep clippy happy
+    #[cfg(not(target_os = "solana"))]
+    let result = {
+        core::hint::black_box(&(sysvar_id, &dst, offset));
+        SYSVAR_NOT_FOUND
+    };
+
+    match result {
+        solana_program::entrypoint::SUCCESS => Ok(()),
+        OFFSET_LENGTH_EXCEEDS_SYSVAR => Err(ProgramError::InvalidArgument),
+        _ => Err(ProgramError::UnsupportedSysvar),
+    }
+}
@@ -0,0 +1,102 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Slot hashes sysvar partial reads.
+//!
+//! The slot hashes sysvar is too large to be deserialized on-chain, so entries are
+//! read individually through the `sol_get_sysvar` syscall:
+//!
+//! * `u64` number of entries
+//! * entries, each as a `u64` slot and a 32-byte hash, ordered from the newest to
+//!   the oldest slot
+
+use solana_program::{clock::Slot, hash::Hash, program_error::ProgramError};
+
+use super::get_into;
+
+pub use solana_program::{slot_hashes::MAX_ENTRIES, sysvar::slot_hashes::ID};
+
+/// Offset of the entries.
+const ENTRIES_OFFSET: usize = std::mem::size_of::<u64>();
+
+/// Size of an entry (slot and hash).
+const ENTRY_LEN: usize = std::mem::size_of::<Slot>() + std::mem::size_of::<Hash>();
+
+/// Returns the number of entries of the slot hashes sysvar.
+///
+/// Returns the error of the syscall if the sysvar cannot be read.
+#[inline(always)]
+pub fn len() -> Result<usize, ProgramError> {
+    let mut len = [0u8; std::mem::size_of::<u64>()];
+    get_into(&ID, 0, &mut len)?;
+
+    Ok(u64::from_le_bytes(len) as usize)
+}
+
+/// Returns the entry at the given index.
+///
+/// Entries are ordered from the newest to the oldest slot.
+///
+/// Returns `ProgramError::InvalidArgument` if the index is out of bounds.
+///
+/// # Arguments
+///
+/// * `index`: Index of the entry.
+pub fn get_entry(index: usize) -> Result<(Slot, Hash), ProgramError> {
+    if index >= len()? {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    read_entry(index)
+}
+
+/// Returns the hash of the given slot.
+///
+/// The entries are binary searched, reading only the entries visited by the
+/// search.
+///
+/// Returns `None` if the slot is not present in the slot hashes sysvar.
+///
+/// # Arguments
+///
+/// * `slot`: Slot to look up.
+pub fn get_hash(slot: Slot) -> Result<Option<Hash>, ProgramError> {
+    let (mut low, mut high) = (0, len()?);
+
+    while low < high {
+        let middle = low + (high - low) / 2;
+        let (entry_slot, hash) = read_entry(middle)?;
+
+        match entry_slot.cmp(&slot) {
+            std::cmp::Ordering::Equal => return Ok(Some(hash)),
+            // entries are in descending slot order
+            std::cmp::Ordering::Greater => low = middle + 1,
+            std::cmp::Ordering::Less => high = middle,
+        }
+    }
+
+    Ok(None)
+}
+
+/// Reads the entry at the given (valid) index.
+#[inline(always)]
+fn read_entry(index: usize) -> Result<(Slot, Hash), ProgramError> {
+    let mut entry = [0u8; ENTRY_LEN];
+    get_into(&ID, ENTRIES_OFFSET + index * ENTRY_LEN, &mut entry)?;
+
+    let slot = u64::from_le_bytes(entry[..8].try_into().unwrap());
+    let hash = Hash::new_from_array(entry[8..].try_into().unwrap());
+
+    Ok((slot, hash))
+}
@@ -0,0 +1,124 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Slot history sysvar access.
+//!
+//! The slot history sysvar contains a bitvector of the slots present over the
+//! last [`MAX_ENTRIES`] slots:
+//!
+//! * `u8` option tag of the bitvector blocks
+//! * `u64` number of blocks
+//! * `u64` blocks, where the bit `slot % MAX_ENTRIES` indicates whether the slot
+//!   is present or not
+//! * `u64` number of bits
+//! * `u64` next slot
+
+use solana_program::{clock::Slot, program_error::ProgramError};
+
+use crate::{AccountInfo, Ref};
+
+pub use solana_program::{
+    slot_history::{Check, MAX_ENTRIES},
+    sysvar::slot_history::ID,
+};
+
+/// Offset of the bitvector blocks.
+const BLOCKS_OFFSET: usize = 1 + std::mem::size_of::<u64>();
+
+/// Number of bitvector blocks.
+const BLOCKS_LEN: usize = MAX_ENTRIES as usize / u64::BITS as usize;
+
+/// Offset of the next slot.
+const NEXT_SLOT_OFFSET: usize =
+    BLOCKS_OFFSET + BLOCKS_LEN * std::mem::size_of::<u64>() + std::mem::size_of::<u64>();
+
+/// Size of the slot history sysvar data.
+pub const SLOT_HISTORY_LEN: usize = NEXT_SLOT_OFFSET + std::mem::size_of::<u64>();
+
+/// Zero-copy view over the slot history sysvar account data.
+pub struct SlotHistory<'a> {
+    /// Data of the sysvar account.
+    data: Ref<'a, [u8]>,
+}
+
+impl<'a> SlotHistory<'a> {
+    /// Creates a view over the slot history sysvar account.
+    ///
+    /// Returns `ProgramError::UnsupportedSysvar` if the account is not the slot
+    /// history sysvar and `ProgramError::InvalidAccountData` if the account data
+    /// does not have the expected length.
+    pub fn try_from(account: &'a AccountInfo) -> Result<Self, ProgramError> {
+        if account.key() != &ID {
+            return Err(ProgramError::UnsupportedSysvar);
+        }
+
+        let data = account.try_borrow_data()?;
+
+        if data.len() != SLOT_HISTORY_LEN {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        Ok(Self { data })
+    }
+
+    /// Returns the next slot.
+    #[inline(always)]
+    pub fn next_slot(&self) -> Slot {
+        self.read_u64(NEXT_SLOT_OFFSET)
+    }
+
+    /// Returns the oldest slot tracked by the slot history.
+    #[inline(always)]
+    pub fn oldest(&self) -> Slot {
+        self.next_slot().saturating_sub(MAX_ENTRIES)
+    }
+
+    /// Returns the newest slot tracked by the slot history.
+    #[inline(always)]
+    pub fn newest(&self) -> Slot {
+        self.next_slot().saturating_sub(1)
+    }
+
+    /// Checks whether the given slot is present in the slot history.
+    ///
+    /// Only the block containing the bit of the slot is read.
+    pub fn check(&self, slot: Slot) -> Check {
+        if slot > self.newest() {
+            Check::Future
+        } else if slot < self.oldest() {
+            Check::TooOld
+        } else {
+            let bit = slot % MAX_ENTRIES;
+            let block = self.read_u64(
+                BLOCKS_OFFSET + (bit / u64::BITS as u64) as usize * std::mem::size_of::<u64>(),
+            );
+
+            if block & (1 << (bit % u64::BITS as u64)) != 0 {
+                Check::Found
+            } else {
+                Check::NotFound
+            }
+        }
+    }
+
+    /// Reads a little-endian `u64` at the given offset.
+    #[inline(always)]
+    fn read_u64(&self, offset: usize) -> u64 {
+        u64::from_le_bytes(
+            self.data[offset..offset + std::mem::size_of::<u64>()]
+                .try_into()
+                .unwrap(),
+        )
+    }
+}
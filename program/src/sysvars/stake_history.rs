@@ -0,0 +1,112 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Stake history sysvar partial reads.
+//!
+//! The stake history sysvar is too large to be deserialized on-chain, so entries
+//! are read individually through the `sol_get_sysvar` syscall:
+//!
+//! * `u64` number of entries
+//! * entries, each as a `u64` epoch and the `u64` effective, activating and
+//!   deactivating stake, ordered from the newest to the oldest epoch
+
+use solana_program::{clock::Epoch, program_error::ProgramError};
+
+use super::get_into;
+
+pub use solana_program::{
+    stake_history::{StakeHistoryEntry, MAX_ENTRIES},
+    sysvar::stake_history::ID,
+};
+
+/// Offset of the entries.
+const ENTRIES_OFFSET: usize = std::mem::size_of::<u64>();
+
+/// Size of an entry (epoch and stake amounts).
+const ENTRY_LEN: usize = 4 * std::mem::size_of::<u64>();
+
+/// Returns the number of entries of the stake history sysvar.
+///
+/// Returns the error of the syscall if the sysvar cannot be read.
+#[inline(always)]
+pub fn len() -> Result<usize, ProgramError> {
+    let mut len = [0u8; std::mem::size_of::<u64>()];
+    get_into(&ID, 0, &mut len)?;
+
+    Ok(u64::from_le_bytes(len) as usize)
+}
+
+/// Returns the entry at the given index.
+///
+/// Entries are ordered from the newest to the oldest epoch.
+///
+/// Returns `ProgramError::InvalidArgument` if the index is out of bounds.
+///
+/// # Arguments
+///
+/// * `index`: Index of the entry.
+pub fn get_entry(index: usize) -> Result<(Epoch, StakeHistoryEntry), ProgramError> {
+    if index >= len()? {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    read_entry(index)
+}
+
+/// Returns the stake history entry of the given epoch.
+///
+/// The entries are binary searched, reading only the entries visited by the
+/// search.
+///
+/// Returns `None` if the epoch is not present in the stake history sysvar.
+///
+/// # Arguments
+///
+/// * `epoch`: Epoch to look up.
+pub fn get(epoch: Epoch) -> Result<Option<StakeHistoryEntry>, ProgramError> {
+    let (mut low, mut high) = (0, len()?);
+
+    while low < high {
+        let middle = low + (high - low) / 2;
+        let (entry_epoch, entry) = read_entry(middle)?;
+
+        match entry_epoch.cmp(&epoch) {
+            std::cmp::Ordering::Equal => return Ok(Some(entry)),
+            // entries are in descending epoch order
+            std::cmp::Ordering::Greater => low = middle + 1,
+            std::cmp::Ordering::Less => high = middle,
+        }
+    }
+
+    Ok(None)
+}
+
+/// Reads the entry at the given (valid) index.
+#[inline(always)]
+fn read_entry(index: usize) -> Result<(Epoch, StakeHistoryEntry), ProgramError> {
+    let mut entry = [0u8; ENTRY_LEN];
+    get_into(&ID, ENTRIES_OFFSET + index * ENTRY_LEN, &mut entry)?;
+
+    let read_u64 =
+        |offset: usize| u64::from_le_bytes(entry[offset..offset + 8].try_into().unwrap());
+
+    Ok((
+        read_u64(0),
+        StakeHistoryEntry {
+            effective: read_u64(8),
+            activating: read_u64(16),
+            deactivating: read_u64(24),
+        },
+    ))
+}
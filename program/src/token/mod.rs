@@ -0,0 +1,1549 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! SPL Token Program CPI functions.
+//!
+//! The helpers take the token program account and work against both the SPL Token
+//! and Token-2022 programs.
+
+pub mod state;
+
+use solana_program::{
+    entrypoint::ProgramResult, program_error::ProgramError, pubkey, pubkey::Pubkey,
+};
+
+use crate::{
+    cpi::{self, invoke_signed_c, CAccountInfo, CAccountMeta, CInstruction, CSigner},
+    token_2022, AccountInfo,
+};
+
+use state::{Mint, TokenAccount};
+
+/// SPL Token program id.
+pub const ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+
+/// Maximum number of signers of a multisig account.
+pub const MAX_SIGNERS: usize = 11;
+
+/// Size of a multisig account.
+pub const MULTISIG_LEN: usize = 355;
+
+/// Maximum number of accounts (excluding the authority and its signers) of an
+/// instruction with an authority.
+const MAX_INSTRUCTION_ACCOUNTS: usize = 4;
+
+/// Authority of a token instruction.
+///
+/// The authority is either a single signer or a multisig account, in which case
+/// the `signers` are the signers of the multisig that sign the instruction.
+#[derive(Clone, Copy)]
+pub struct Authority<'a> {
+    /// Authority account (owner, delegate or multisig).
+    account: &'a AccountInfo,
+
+    /// Signers of the multisig (empty for a single signer).
+    signers: &'a [&'a AccountInfo],
+}
+
+impl<'a> Authority<'a> {
+    /// Creates a single signer authority.
+    #[inline(always)]
+    pub const fn single(account: &'a AccountInfo) -> Self {
+        Self {
+            account,
+            signers: &[],
+        }
+    }
+
+    /// Creates a multisig authority.
+    ///
+    /// Returns `ProgramError::InvalidArgument` if there are more than `MAX_SIGNERS`
+    /// signers.
+    #[inline(always)]
+    pub fn multisig(
+        account: &'a AccountInfo,
+        signers: &'a [&'a AccountInfo],
+    ) -> Result<Self, ProgramError> {
+        if signers.len() > MAX_SIGNERS {
+            return Err(ProgramError::InvalidArgument);
+        }
+
+        Ok(Self { account, signers })
+    }
+
+    /// Returns the authority account (owner, delegate or multisig).
+    #[inline(always)]
+    pub fn account(&self) -> &'a AccountInfo {
+        self.account
+    }
+
+    /// Returns the signers of the multisig (empty for a single signer).
+    #[inline(always)]
+    pub fn signers(&self) -> &'a [&'a AccountInfo] {
+        self.signers
+    }
+
+    /// Indicates whether the authority is a multisig.
+    #[inline(always)]
+    pub fn is_multisig(&self) -> bool {
+        !self.signers.is_empty()
+    }
+}
+
+impl<'a> From<&'a AccountInfo> for Authority<'a> {
+    fn from(account: &'a AccountInfo) -> Self {
+        Self::single(account)
+    }
+}
+
+/// Type of authority of a mint or token account.
+///
+/// Authority types after `CloseAccount` are only supported by the Token-2022
+/// program.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AuthorityType {
+    /// Authority to mint new tokens.
+    MintTokens = 0,
+
+    /// Authority to freeze token accounts of a mint.
+    FreezeAccount = 1,
+
+    /// Owner of a token account.
+    AccountOwner = 2,
+
+    /// Authority to close a token account.
+    CloseAccount = 3,
+
+    /// Authority to set the transfer fee of a mint.
+    TransferFeeConfig = 4,
+
+    /// Authority to withdraw withheld transfer fees.
+    WithheldWithdraw = 5,
+
+    /// Authority to close a mint.
+    CloseMint = 6,
+
+    /// Authority to set the interest rate of a mint.
+    InterestRate = 7,
+
+    /// Permanent delegate of a mint.
+    PermanentDelegate = 8,
+
+    /// Authority to update the confidential transfer configuration of a mint.
+    ConfidentialTransferMint = 9,
+
+    /// Authority to set the transfer hook program of a mint.
+    TransferHookProgramId = 10,
+
+    /// Authority to update the confidential transfer fee configuration of a mint.
+    ConfidentialTransferFeeConfig = 11,
+
+    /// Authority to set the metadata address of a mint.
+    MetadataPointer = 12,
+
+    /// Authority to set the group address of a mint.
+    GroupPointer = 13,
+
+    /// Authority to set the group member address of a mint.
+    GroupMemberPointer = 14,
+}
+
+/// Initialize a multisig account.
+///
+/// This uses the `InitializeMultisig2` instruction, which does not require the rent
+/// sysvar account.
+///
+/// Returns `ProgramError::InvalidArgument` if there are more than `MAX_SIGNERS`
+/// signers.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `multisig`: Multisig account to initialize.
+/// * `signers`: Signers of the multisig.
+/// * `m`: Number of signers required to sign an instruction.
+pub fn initialize_multisig(
+    token_program: &AccountInfo,
+    multisig: &AccountInfo,
+    signers: &[&AccountInfo],
+    m: u8,
+) -> ProgramResult {
+    if signers.len() > MAX_SIGNERS {
+        return Err(ProgramError::InvalidArgument);
+    }
+
+    // metas and account infos of the multisig and signers
+    let mut instruction_accounts: [std::mem::MaybeUninit<CAccountMeta>; 1 + MAX_SIGNERS] =
+        unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+    let mut account_infos: [std::mem::MaybeUninit<CAccountInfo>; 1 + MAX_SIGNERS] =
+        unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+
+    std::iter::once(multisig)
+        .chain(signers.iter().copied())
+        .enumerate()
+        .for_each(|(i, account)| {
+            instruction_accounts[i] = std::mem::MaybeUninit::new(CAccountMeta {
+                pubkey: account.key(),
+                is_writable: i == 0,
+                is_signer: false,
+            });
+            account_infos[i] = std::mem::MaybeUninit::new(account.into());
+        });
+
+    let accounts_len = 1 + signers.len();
+
+    // -   0: instruction discriminator
+    // -   1: number of required signers
+    //
+    // initialize multisig 2 instruction has a '19' discriminator
+    let instruction_data = [19, m];
+
+    let instruction = CInstruction {
+        program_id: token_program_id(token_program)?,
+        accounts: instruction_accounts.as_ptr() as *const CAccountMeta,
+        accounts_len: accounts_len as u64,
+        data: instruction_data.as_ptr(),
+        data_len: instruction_data.len() as u64,
+    };
+
+    invoke_signed_c(
+        &instruction,
+        unsafe {
+            std::slice::from_raw_parts(account_infos.as_ptr() as *const CAccountInfo, accounts_len)
+        },
+        &[],
+    )
+}
+
+/// Initialize a mint account.
+///
+/// This uses the `InitializeMint2` instruction, which does not require the rent
+/// sysvar account.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `mint`: Mint account to initialize.
+/// * `decimals`: Number of decimals of the mint.
+/// * `mint_authority`: Authority that can mint new tokens.
+/// * `freeze_authority`: Authority that can freeze token accounts.
+pub fn initialize_mint2(
+    token_program: &AccountInfo,
+    mint: &AccountInfo,
+    decimals: u8,
+    mint_authority: &Pubkey,
+    freeze_authority: Option<&Pubkey>,
+) -> ProgramResult {
+    let instruction_accounts: [CAccountMeta; 1] = [mint.into()];
+
+    // -      0: instruction discriminator
+    // -      1: decimals
+    // -  2..34: mint authority
+    // -     34: freeze authority option (0 = none, 1 = some)
+    // - 35..67: freeze authority (only present when set)
+    let mut instruction_data = [0; 67];
+    // initialize mint 2 instruction has a '20' discriminator
+    instruction_data[0] = 20;
+    instruction_data[1] = decimals;
+    instruction_data[2..34].copy_from_slice(mint_authority.as_ref());
+
+    let data_len = if let Some(freeze_authority) = freeze_authority {
+        instruction_data[34] = 1;
+        instruction_data[35..67].copy_from_slice(freeze_authority.as_ref());
+        67
+    } else {
+        35
+    };
+
+    let instruction = CInstruction {
+        program_id: token_program_id(token_program)?,
+        accounts: instruction_accounts.as_ptr(),
+        accounts_len: instruction_accounts.len() as u64,
+        data: instruction_data.as_ptr(),
+        data_len: data_len as u64,
+    };
+
+    let account_infos: [CAccountInfo; 1] = [mint.into()];
+
+    invoke_signed_c(&instruction, &account_infos, &[])
+}
+
+/// Initialize a token account.
+///
+/// This uses the `InitializeAccount3` instruction, which does not require the rent
+/// sysvar account and takes the owner as instruction data.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `account`: Token account to initialize.
+/// * `mint`: Mint of the token account.
+/// * `owner`: Owner of the token account.
+pub fn initialize_account3(
+    token_program: &AccountInfo,
+    account: &AccountInfo,
+    mint: &AccountInfo,
+    owner: &Pubkey,
+) -> ProgramResult {
+    let instruction_accounts: [CAccountMeta; 2] = [account.into(), mint.into()];
+
+    // -     0: instruction discriminator
+    // - 1..33: owner
+    let mut instruction_data = [0; 33];
+    // initialize account 3 instruction has a '18' discriminator
+    instruction_data[0] = 18;
+    instruction_data[1..33].copy_from_slice(owner.as_ref());
+
+    let instruction = CInstruction {
+        program_id: token_program_id(token_program)?,
+        accounts: instruction_accounts.as_ptr(),
+        accounts_len: instruction_accounts.len() as u64,
+        data: instruction_data.as_ptr(),
+        data_len: instruction_data.len() as u64,
+    };
+
+    let account_infos: [CAccountInfo; 2] = [account.into(), mint.into()];
+
+    invoke_signed_c(&instruction, &account_infos, &[])
+}
+
+/// Sync the amount of a native token account with its lamports balance.
+///
+/// This is required after lamports are transferred directly to a wrapped SOL
+/// token account.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `account`: Native token account to sync.
+pub fn sync_native(token_program: &AccountInfo, account: &AccountInfo) -> ProgramResult {
+    let instruction_accounts: [CAccountMeta; 1] = [account.into()];
+
+    // sync native instruction has a '17' discriminator
+    let instruction_data = [17];
+
+    let instruction = CInstruction {
+        program_id: token_program_id(token_program)?,
+        accounts: instruction_accounts.as_ptr(),
+        accounts_len: instruction_accounts.len() as u64,
+        data: instruction_data.as_ptr(),
+        data_len: instruction_data.len() as u64,
+    };
+
+    let account_infos: [CAccountInfo; 1] = [account.into()];
+
+    invoke_signed_c(&instruction, &account_infos, &[])
+}
+
+/// Transfer tokens from one token account to another.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `source`: Token account to transfer from.
+/// * `destination`: Token account to transfer to.
+/// * `authority`: Owner or delegate of the source account.
+/// * `amount`: Amount of tokens to transfer.
+#[inline(always)]
+pub fn transfer(
+    token_program: &AccountInfo,
+    source: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+) -> ProgramResult {
+    _transfer_signed(token_program, source, destination, authority, amount, &[])
+}
+
+/// Transfer tokens from one token account to another with a program signed
+/// instruction.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `source`: Token account to transfer from.
+/// * `destination`: Token account to transfer to.
+/// * `authority`: Owner or delegate of the source account.
+/// * `amount`: Amount of tokens to transfer.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+#[inline(always)]
+pub fn transfer_signed<const SEEDS: usize>(
+    token_program: &AccountInfo,
+    source: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _transfer_signed(
+        token_program,
+        source,
+        destination,
+        authority,
+        amount,
+        &signer,
+    )
+}
+
+/// Transfer tokens from one token account to another, checking the mint and decimals.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `source`: Token account to transfer from.
+/// * `mint`: Mint of the token accounts.
+/// * `destination`: Token account to transfer to.
+/// * `authority`: Owner or delegate of the source account.
+/// * `amount`: Amount of tokens to transfer.
+/// * `decimals`: Expected number of decimals of the mint.
+#[inline(always)]
+pub fn transfer_checked(
+    token_program: &AccountInfo,
+    source: &AccountInfo,
+    mint: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    decimals: u8,
+) -> ProgramResult {
+    _transfer_checked_signed(
+        token_program,
+        source,
+        mint,
+        destination,
+        authority,
+        amount,
+        decimals,
+        &[],
+    )
+}
+
+/// Transfer tokens from one token account to another, checking the mint and decimals,
+/// with a program signed instruction.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `source`: Token account to transfer from.
+/// * `mint`: Mint of the token accounts.
+/// * `destination`: Token account to transfer to.
+/// * `authority`: Owner or delegate of the source account.
+/// * `amount`: Amount of tokens to transfer.
+/// * `decimals`: Expected number of decimals of the mint.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+#[allow(clippy::too_many_arguments)]
+#[inline(always)]
+pub fn transfer_checked_signed<const SEEDS: usize>(
+    token_program: &AccountInfo,
+    source: &AccountInfo,
+    mint: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    decimals: u8,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _transfer_checked_signed(
+        token_program,
+        source,
+        mint,
+        destination,
+        authority,
+        amount,
+        decimals,
+        &signer,
+    )
+}
+
+/// Mint new tokens to a token account.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `mint`: Mint account.
+/// * `destination`: Token account to mint to.
+/// * `authority`: Mint authority of the mint.
+/// * `amount`: Amount of tokens to mint.
+#[inline(always)]
+pub fn mint_to(
+    token_program: &AccountInfo,
+    mint: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+) -> ProgramResult {
+    _mint_to_signed(token_program, mint, destination, authority, amount, &[])
+}
+
+/// Mint new tokens to a token account with a program signed instruction.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `mint`: Mint account.
+/// * `destination`: Token account to mint to.
+/// * `authority`: Mint authority of the mint.
+/// * `amount`: Amount of tokens to mint.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+#[inline(always)]
+pub fn mint_to_signed<const SEEDS: usize>(
+    token_program: &AccountInfo,
+    mint: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _mint_to_signed(token_program, mint, destination, authority, amount, &signer)
+}
+
+/// Burn tokens from a token account.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `account`: Token account to burn from.
+/// * `mint`: Mint of the token account.
+/// * `authority`: Owner or delegate of the token account.
+/// * `amount`: Amount of tokens to burn.
+#[inline(always)]
+pub fn burn(
+    token_program: &AccountInfo,
+    account: &AccountInfo,
+    mint: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+) -> ProgramResult {
+    _burn_signed(token_program, account, mint, authority, amount, &[])
+}
+
+/// Burn tokens from a token account with a program signed instruction.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `account`: Token account to burn from.
+/// * `mint`: Mint of the token account.
+/// * `authority`: Owner or delegate of the token account.
+/// * `amount`: Amount of tokens to burn.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+#[inline(always)]
+pub fn burn_signed<const SEEDS: usize>(
+    token_program: &AccountInfo,
+    account: &AccountInfo,
+    mint: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _burn_signed(token_program, account, mint, authority, amount, &signer)
+}
+
+/// Close a token account, transferring its lamports to the destination account.
+///
+/// The token account must have a zero balance (unless it is a native account).
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `account`: Token account to close.
+/// * `destination`: Account to receive the lamports of the closed account.
+/// * `authority`: Owner or close authority of the token account.
+#[inline(always)]
+pub fn close_account(
+    token_program: &AccountInfo,
+    account: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+) -> ProgramResult {
+    _close_account_signed(token_program, account, destination, authority, &[])
+}
+
+/// Close a token account, transferring its lamports to the destination account,
+/// with a program signed instruction.
+///
+/// The token account must have a zero balance (unless it is a native account).
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `account`: Token account to close.
+/// * `destination`: Account to receive the lamports of the closed account.
+/// * `authority`: Owner or close authority of the token account.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+#[inline(always)]
+pub fn close_account_signed<const SEEDS: usize>(
+    token_program: &AccountInfo,
+    account: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _close_account_signed(token_program, account, destination, authority, &signer)
+}
+
+/// Approve a delegate to transfer or burn tokens from a token account.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `source`: Token account of the owner.
+/// * `delegate`: Account of the delegate.
+/// * `authority`: Owner of the token account.
+/// * `amount`: Amount of tokens the delegate is approved for.
+#[inline(always)]
+pub fn approve(
+    token_program: &AccountInfo,
+    source: &AccountInfo,
+    delegate: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+) -> ProgramResult {
+    _approve_signed(token_program, source, delegate, authority, amount, &[])
+}
+
+/// Approve a delegate to transfer or burn tokens from a token account with a
+/// program signed instruction.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `source`: Token account of the owner.
+/// * `delegate`: Account of the delegate.
+/// * `authority`: Owner of the token account.
+/// * `amount`: Amount of tokens the delegate is approved for.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+#[inline(always)]
+pub fn approve_signed<const SEEDS: usize>(
+    token_program: &AccountInfo,
+    source: &AccountInfo,
+    delegate: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _approve_signed(token_program, source, delegate, authority, amount, &signer)
+}
+
+/// Approve a delegate to transfer or burn tokens from a token account, checking the
+/// mint and decimals.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `source`: Token account of the owner.
+/// * `mint`: Mint of the token account.
+/// * `delegate`: Account of the delegate.
+/// * `authority`: Owner of the token account.
+/// * `amount`: Amount of tokens the delegate is approved for.
+/// * `decimals`: Expected number of decimals of the mint.
+#[inline(always)]
+pub fn approve_checked(
+    token_program: &AccountInfo,
+    source: &AccountInfo,
+    mint: &AccountInfo,
+    delegate: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    decimals: u8,
+) -> ProgramResult {
+    _approve_checked_signed(
+        token_program,
+        source,
+        mint,
+        delegate,
+        authority,
+        amount,
+        decimals,
+        &[],
+    )
+}
+
+/// Approve a delegate to transfer or burn tokens from a token account, checking the
+/// mint and decimals, with a program signed instruction.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `source`: Token account of the owner.
+/// * `mint`: Mint of the token account.
+/// * `delegate`: Account of the delegate.
+/// * `authority`: Owner of the token account.
+/// * `amount`: Amount of tokens the delegate is approved for.
+/// * `decimals`: Expected number of decimals of the mint.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+#[allow(clippy::too_many_arguments)]
+#[inline(always)]
+pub fn approve_checked_signed<const SEEDS: usize>(
+    token_program: &AccountInfo,
+    source: &AccountInfo,
+    mint: &AccountInfo,
+    delegate: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    decimals: u8,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _approve_checked_signed(
+        token_program,
+        source,
+        mint,
+        delegate,
+        authority,
+        amount,
+        decimals,
+        &signer,
+    )
+}
+
+/// Revoke the delegate of a token account.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `source`: Token account of the owner.
+/// * `authority`: Owner of the token account.
+#[inline(always)]
+pub fn revoke(
+    token_program: &AccountInfo,
+    source: &AccountInfo,
+    authority: Authority,
+) -> ProgramResult {
+    _revoke_signed(token_program, source, authority, &[])
+}
+
+/// Revoke the delegate of a token account with a program signed instruction.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `source`: Token account of the owner.
+/// * `authority`: Owner of the token account.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+#[inline(always)]
+pub fn revoke_signed<const SEEDS: usize>(
+    token_program: &AccountInfo,
+    source: &AccountInfo,
+    authority: Authority,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _revoke_signed(token_program, source, authority, &signer)
+}
+
+/// Freeze a token account.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `account`: Token account to freeze.
+/// * `mint`: Mint of the token account.
+/// * `authority`: Freeze authority of the mint.
+#[inline(always)]
+pub fn freeze_account(
+    token_program: &AccountInfo,
+    account: &AccountInfo,
+    mint: &AccountInfo,
+    authority: Authority,
+) -> ProgramResult {
+    _freeze_account_signed(token_program, account, mint, authority, &[])
+}
+
+/// Freeze a token account with a program signed instruction.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `account`: Token account to freeze.
+/// * `mint`: Mint of the token account.
+/// * `authority`: Freeze authority of the mint.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+#[inline(always)]
+pub fn freeze_account_signed<const SEEDS: usize>(
+    token_program: &AccountInfo,
+    account: &AccountInfo,
+    mint: &AccountInfo,
+    authority: Authority,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _freeze_account_signed(token_program, account, mint, authority, &signer)
+}
+
+/// Thaw a token account.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `account`: Token account to thaw.
+/// * `mint`: Mint of the token account.
+/// * `authority`: Freeze authority of the mint.
+#[inline(always)]
+pub fn thaw_account(
+    token_program: &AccountInfo,
+    account: &AccountInfo,
+    mint: &AccountInfo,
+    authority: Authority,
+) -> ProgramResult {
+    _thaw_account_signed(token_program, account, mint, authority, &[])
+}
+
+/// Thaw a token account with a program signed instruction.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `account`: Token account to thaw.
+/// * `mint`: Mint of the token account.
+/// * `authority`: Freeze authority of the mint.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+#[inline(always)]
+pub fn thaw_account_signed<const SEEDS: usize>(
+    token_program: &AccountInfo,
+    account: &AccountInfo,
+    mint: &AccountInfo,
+    authority: Authority,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _thaw_account_signed(token_program, account, mint, authority, &signer)
+}
+
+/// Set a new authority of a mint or token account.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `account`: Mint or token account.
+/// * `authority`: Current authority of the account.
+/// * `authority_type`: Type of the authority to set.
+/// * `new_authority`: New authority (`None` removes the authority).
+#[inline(always)]
+pub fn set_authority(
+    token_program: &AccountInfo,
+    account: &AccountInfo,
+    authority: Authority,
+    authority_type: AuthorityType,
+    new_authority: Option<&Pubkey>,
+) -> ProgramResult {
+    _set_authority_signed(
+        token_program,
+        account,
+        authority,
+        authority_type,
+        new_authority,
+        &[],
+    )
+}
+
+/// Set a new authority of a mint or token account with a program signed
+/// instruction.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `account`: Mint or token account.
+/// * `authority`: Current authority of the account.
+/// * `authority_type`: Type of the authority to set.
+/// * `new_authority`: New authority (`None` removes the authority).
+/// * `signer_seeds`: Seeds used to sign the instruction.
+#[inline(always)]
+pub fn set_authority_signed<const SEEDS: usize>(
+    token_program: &AccountInfo,
+    account: &AccountInfo,
+    authority: Authority,
+    authority_type: AuthorityType,
+    new_authority: Option<&Pubkey>,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _set_authority_signed(
+        token_program,
+        account,
+        authority,
+        authority_type,
+        new_authority,
+        &signer,
+    )
+}
+
+/// Mint new tokens to a token account, checking the decimals.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `mint`: Mint account.
+/// * `destination`: Token account to mint to.
+/// * `authority`: Mint authority of the mint.
+/// * `amount`: Amount of tokens to mint.
+/// * `decimals`: Expected number of decimals of the mint.
+#[inline(always)]
+pub fn mint_to_checked(
+    token_program: &AccountInfo,
+    mint: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    decimals: u8,
+) -> ProgramResult {
+    _mint_to_checked_signed(
+        token_program,
+        mint,
+        destination,
+        authority,
+        amount,
+        decimals,
+        &[],
+    )
+}
+
+/// Mint new tokens to a token account, checking the decimals, with a program
+/// signed instruction.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `mint`: Mint account.
+/// * `destination`: Token account to mint to.
+/// * `authority`: Mint authority of the mint.
+/// * `amount`: Amount of tokens to mint.
+/// * `decimals`: Expected number of decimals of the mint.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+#[inline(always)]
+pub fn mint_to_checked_signed<const SEEDS: usize>(
+    token_program: &AccountInfo,
+    mint: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    decimals: u8,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _mint_to_checked_signed(
+        token_program,
+        mint,
+        destination,
+        authority,
+        amount,
+        decimals,
+        &signer,
+    )
+}
+
+/// Burn tokens from a token account, checking the decimals.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `account`: Token account to burn from.
+/// * `mint`: Mint of the token account.
+/// * `authority`: Owner or delegate of the token account.
+/// * `amount`: Amount of tokens to burn.
+/// * `decimals`: Expected number of decimals of the mint.
+#[inline(always)]
+pub fn burn_checked(
+    token_program: &AccountInfo,
+    account: &AccountInfo,
+    mint: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    decimals: u8,
+) -> ProgramResult {
+    _burn_checked_signed(
+        token_program,
+        account,
+        mint,
+        authority,
+        amount,
+        decimals,
+        &[],
+    )
+}
+
+/// Burn tokens from a token account, checking the decimals, with a program
+/// signed instruction.
+///
+/// # Arguments
+///
+/// * `token_program`: Token program account (SPL Token or Token-2022).
+/// * `account`: Token account to burn from.
+/// * `mint`: Mint of the token account.
+/// * `authority`: Owner or delegate of the token account.
+/// * `amount`: Amount of tokens to burn.
+/// * `decimals`: Expected number of decimals of the mint.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+#[inline(always)]
+pub fn burn_checked_signed<const SEEDS: usize>(
+    token_program: &AccountInfo,
+    account: &AccountInfo,
+    mint: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    decimals: u8,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _burn_checked_signed(
+        token_program,
+        account,
+        mint,
+        authority,
+        amount,
+        decimals,
+        &signer,
+    )
+}
+
+/// Checks that an account is a token account of the expected mint and owner.
+///
+/// Returns the errors of [`TokenAccount::try_from`] if the account is not an
+/// initialized token account, `ProgramError::InvalidAccountData` if the mint does
+/// not match and `ProgramError::IllegalOwner` if the owner does not match.
+///
+/// # Arguments
+///
+/// * `account`: Token account.
+/// * `expected_mint`: Expected mint of the token account.
+/// * `expected_owner`: Expected owner of the token account.
+pub fn assert_token_account(
+    account: &AccountInfo,
+    expected_mint: &Pubkey,
+    expected_owner: &Pubkey,
+) -> ProgramResult {
+    let token_account = TokenAccount::try_from(account)?;
+
+    if token_account.mint() != expected_mint {
+        #[cfg(feature = "logging")]
+        solana_program::msg!("🔴 Invalid mint of token account {}", account.key());
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    if token_account.owner() != expected_owner {
+        #[cfg(feature = "logging")]
+        solana_program::msg!("🔴 Invalid owner of token account {}", account.key());
+        return Err(ProgramError::IllegalOwner);
+    }
+
+    Ok(())
+}
+
+/// Checks that an account is a mint with the expected mint authority.
+///
+/// Returns the errors of [`Mint::try_from`] if the account is not an initialized
+/// mint and `ProgramError::InvalidAccountData` if the mint authority does not match.
+///
+/// # Arguments
+///
+/// * `account`: Mint account.
+/// * `expected_authority`: Expected mint authority of the mint.
+pub fn assert_mint(account: &AccountInfo, expected_authority: &Pubkey) -> ProgramResult {
+    let mint = Mint::try_from(account)?;
+
+    if mint.mint_authority() != Some(expected_authority) {
+        #[cfg(feature = "logging")]
+        solana_program::msg!("🔴 Invalid mint authority of mint {}", account.key());
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    Ok(())
+}
+
+//-- Internal functions
+
+/// Returns the program id of a token program account.
+///
+/// Returns `ProgramError::IncorrectProgramId` if the account is neither the SPL Token
+/// nor the Token-2022 program.
+#[inline(always)]
+fn token_program_id(token_program: &AccountInfo) -> Result<&Pubkey, ProgramError> {
+    let program_id = token_program.key();
+
+    if program_id != &ID && program_id != &token_2022::ID {
+        #[cfg(feature = "logging")]
+        solana_program::msg!("🔴 Invalid token program: {}", program_id);
+        return Err(ProgramError::IncorrectProgramId);
+    }
+
+    Ok(program_id)
+}
+
+/// Invokes an instruction signed by an authority.
+///
+/// The metas of the `accounts` are followed by the authority and the signers of a
+/// multisig authority.
+///
+/// # Arguments
+///
+/// * `program_id`: Token program id.
+/// * `accounts`: Accounts of the instruction (up to `MAX_INSTRUCTION_ACCOUNTS`).
+/// * `authority`: Authority of the instruction.
+/// * `instruction_data`: Instruction data.
+/// * `signers`: Seeds used to sign the instruction.
+pub(crate) fn invoke_with_authority(
+    program_id: &Pubkey,
+    accounts: &[&AccountInfo],
+    authority: &Authority,
+    instruction_data: &[u8],
+    signers: &[CSigner],
+) -> ProgramResult {
+    debug_assert!(accounts.len() <= MAX_INSTRUCTION_ACCOUNTS);
+
+    let mut instruction_accounts: [std::mem::MaybeUninit<CAccountMeta>;
+        MAX_INSTRUCTION_ACCOUNTS + 1 + MAX_SIGNERS] =
+        unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+    let mut account_infos: [std::mem::MaybeUninit<CAccountInfo>;
+        MAX_INSTRUCTION_ACCOUNTS + 1 + MAX_SIGNERS] =
+        unsafe { std::mem::MaybeUninit::uninit().assume_init() };
+
+    accounts
+        .iter()
+        .chain(std::iter::once(&authority.account))
+        .chain(authority.signers.iter())
+        .enumerate()
+        .for_each(|(i, account)| {
+            let mut meta: CAccountMeta = (*account).into();
+            // the authority (or the signers of a multisig) must sign the instruction
+            if i == accounts.len() {
+                meta.is_signer = !authority.is_multisig();
+            } else if i > accounts.len() {
+                meta.is_signer = true;
+            }
+
+            instruction_accounts[i] = std::mem::MaybeUninit::new(meta);
+            account_infos[i] = std::mem::MaybeUninit::new((*account).into());
+        });
+
+    let accounts_len = accounts.len() + 1 + authority.signers.len();
+
+    let instruction = CInstruction {
+        program_id,
+        accounts: instruction_accounts.as_ptr() as *const CAccountMeta,
+        accounts_len: accounts_len as u64,
+        data: instruction_data.as_ptr(),
+        data_len: instruction_data.len() as u64,
+    };
+
+    invoke_signed_c(
+        &instruction,
+        unsafe {
+            std::slice::from_raw_parts(account_infos.as_ptr() as *const CAccountInfo, accounts_len)
+        },
+        signers,
+    )
+}
+
+/// Transfer tokens from one token account to another either with or without a
+/// program signed instruction.
+fn _transfer_signed(
+    token_program: &AccountInfo,
+    source: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // -    0: instruction discriminator
+    // - 1..9: amount
+    let mut instruction_data = [0; 9];
+    // transfer instruction has a '3' discriminator
+    instruction_data[0] = 3;
+    instruction_data[1..9].copy_from_slice(&amount.to_le_bytes());
+
+    invoke_with_authority(
+        token_program_id(token_program)?,
+        &[source, destination],
+        &authority,
+        &instruction_data,
+        signer,
+    )
+}
+
+/// Transfer tokens from one token account to another, checking the mint and decimals,
+/// either with or without a program signed instruction.
+#[allow(clippy::too_many_arguments)]
+fn _transfer_checked_signed(
+    token_program: &AccountInfo,
+    source: &AccountInfo,
+    mint: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    decimals: u8,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // -     0: instruction discriminator
+    // -  1..9: amount
+    // -     9: decimals
+    let mut instruction_data = [0; 10];
+    // transfer checked instruction has a '12' discriminator
+    instruction_data[0] = 12;
+    instruction_data[1..9].copy_from_slice(&amount.to_le_bytes());
+    instruction_data[9] = decimals;
+
+    invoke_with_authority(
+        token_program_id(token_program)?,
+        &[source, mint, destination],
+        &authority,
+        &instruction_data,
+        signer,
+    )
+}
+
+/// Mint new tokens to a token account either with or without a program signed
+/// instruction.
+fn _mint_to_signed(
+    token_program: &AccountInfo,
+    mint: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // -    0: instruction discriminator
+    // - 1..9: amount
+    let mut instruction_data = [0; 9];
+    // mint to instruction has a '7' discriminator
+    instruction_data[0] = 7;
+    instruction_data[1..9].copy_from_slice(&amount.to_le_bytes());
+
+    invoke_with_authority(
+        token_program_id(token_program)?,
+        &[mint, destination],
+        &authority,
+        &instruction_data,
+        signer,
+    )
+}
+
+/// Burn tokens from a token account either with or without a program signed
+/// instruction.
+fn _burn_signed(
+    token_program: &AccountInfo,
+    account: &AccountInfo,
+    mint: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // -    0: instruction discriminator
+    // - 1..9: amount
+    let mut instruction_data = [0; 9];
+    // burn instruction has a '8' discriminator
+    instruction_data[0] = 8;
+    instruction_data[1..9].copy_from_slice(&amount.to_le_bytes());
+
+    invoke_with_authority(
+        token_program_id(token_program)?,
+        &[account, mint],
+        &authority,
+        &instruction_data,
+        signer,
+    )
+}
+
+/// Close a token account either with or without a program signed instruction.
+fn _close_account_signed(
+    token_program: &AccountInfo,
+    account: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // close account instruction has a '9' discriminator
+    invoke_with_authority(
+        token_program_id(token_program)?,
+        &[account, destination],
+        &authority,
+        &[9],
+        signer,
+    )
+}
+
+/// Approve a delegate either with or without a program signed instruction.
+fn _approve_signed(
+    token_program: &AccountInfo,
+    source: &AccountInfo,
+    delegate: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // -    0: instruction discriminator
+    // - 1..9: amount
+    let mut instruction_data = [0; 9];
+    // approve instruction has a '4' discriminator
+    instruction_data[0] = 4;
+    instruction_data[1..9].copy_from_slice(&amount.to_le_bytes());
+
+    invoke_with_authority(
+        token_program_id(token_program)?,
+        &[source, delegate],
+        &authority,
+        &instruction_data,
+        signer,
+    )
+}
+
+/// Approve a delegate, checking the mint and decimals, either with or without a
+/// program signed instruction.
+#[allow(clippy::too_many_arguments)]
+fn _approve_checked_signed(
+    token_program: &AccountInfo,
+    source: &AccountInfo,
+    mint: &AccountInfo,
+    delegate: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    decimals: u8,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // -    0: instruction discriminator
+    // - 1..9: amount
+    // -    9: decimals
+    let mut instruction_data = [0; 10];
+    // approve checked instruction has a '13' discriminator
+    instruction_data[0] = 13;
+    instruction_data[1..9].copy_from_slice(&amount.to_le_bytes());
+    instruction_data[9] = decimals;
+
+    invoke_with_authority(
+        token_program_id(token_program)?,
+        &[source, mint, delegate],
+        &authority,
+        &instruction_data,
+        signer,
+    )
+}
+
+/// Revoke the delegate of a token account either with or without a program signed
+/// instruction.
+fn _revoke_signed(
+    token_program: &AccountInfo,
+    source: &AccountInfo,
+    authority: Authority,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // revoke instruction has a '5' discriminator
+    invoke_with_authority(
+        token_program_id(token_program)?,
+        &[source],
+        &authority,
+        &[5],
+        signer,
+    )
+}
+
+/// Freeze a token account either with or without a program signed instruction.
+fn _freeze_account_signed(
+    token_program: &AccountInfo,
+    account: &AccountInfo,
+    mint: &AccountInfo,
+    authority: Authority,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // freeze account instruction has a '10' discriminator
+    invoke_with_authority(
+        token_program_id(token_program)?,
+        &[account, mint],
+        &authority,
+        &[10],
+        signer,
+    )
+}
+
+/// Thaw a token account either with or without a program signed instruction.
+fn _thaw_account_signed(
+    token_program: &AccountInfo,
+    account: &AccountInfo,
+    mint: &AccountInfo,
+    authority: Authority,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // thaw account instruction has a '11' discriminator
+    invoke_with_authority(
+        token_program_id(token_program)?,
+        &[account, mint],
+        &authority,
+        &[11],
+        signer,
+    )
+}
+
+/// Set a new authority of a mint or token account either with or without a program
+/// signed instruction.
+fn _set_authority_signed(
+    token_program: &AccountInfo,
+    account: &AccountInfo,
+    authority: Authority,
+    authority_type: AuthorityType,
+    new_authority: Option<&Pubkey>,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // -     0: instruction discriminator
+    // -     1: authority type
+    // -     2: new authority option (0 = none, 1 = some)
+    // - 3..35: new authority (only present when set)
+    let mut instruction_data = [0; 35];
+    // set authority instruction has a '6' discriminator
+    instruction_data[0] = 6;
+    instruction_data[1] = authority_type as u8;
+
+    let data_len = if let Some(new_authority) = new_authority {
+        instruction_data[2] = 1;
+        instruction_data[3..35].copy_from_slice(new_authority.as_ref());
+        35
+    } else {
+        3
+    };
+
+    invoke_with_authority(
+        token_program_id(token_program)?,
+        &[account],
+        &authority,
+        &instruction_data[..data_len],
+        signer,
+    )
+}
+
+/// Mint new tokens to a token account, checking the decimals, either with or
+/// without a program signed instruction.
+fn _mint_to_checked_signed(
+    token_program: &AccountInfo,
+    mint: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    decimals: u8,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // -    0: instruction discriminator
+    // - 1..9: amount
+    // -    9: decimals
+    let mut instruction_data = [0; 10];
+    // mint to checked instruction has a '14' discriminator
+    instruction_data[0] = 14;
+    instruction_data[1..9].copy_from_slice(&amount.to_le_bytes());
+    instruction_data[9] = decimals;
+
+    invoke_with_authority(
+        token_program_id(token_program)?,
+        &[mint, destination],
+        &authority,
+        &instruction_data,
+        signer,
+    )
+}
+
+/// Burn tokens from a token account, checking the decimals, either with or
+/// without a program signed instruction.
+fn _burn_checked_signed(
+    token_program: &AccountInfo,
+    account: &AccountInfo,
+    mint: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    decimals: u8,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // -    0: instruction discriminator
+    // - 1..9: amount
+    // -    9: decimals
+    let mut instruction_data = [0; 10];
+    // burn checked instruction has a '15' discriminator
+    instruction_data[0] = 15;
+    instruction_data[1..9].copy_from_slice(&amount.to_le_bytes());
+    instruction_data[9] = decimals;
+
+    invoke_with_authority(
+        token_program_id(token_program)?,
+        &[account, mint],
+        &authority,
+        &instruction_data,
+        signer,
+    )
+}
@@ -0,0 +1,264 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Zero-copy views over SPL Token and Token-2022 account state.
+
+use solana_program::{program_error::ProgramError, pubkey::Pubkey};
+
+use crate::{token_2022, AccountInfo, Ref};
+
+use super::{ID, MULTISIG_LEN};
+
+/// Size of a token account (without extensions).
+pub const ACCOUNT_LEN: usize = 165;
+
+/// Size of a mint (without extensions).
+pub const MINT_LEN: usize = 82;
+
+/// Offset of the account type of accounts with extensions.
+const ACCOUNT_TYPE_OFFSET: usize = ACCOUNT_LEN;
+
+/// Account type of a mint with extensions.
+const MINT_ACCOUNT_TYPE: u8 = 1;
+
+/// Account type of a token account with extensions.
+const TOKEN_ACCOUNT_TYPE: u8 = 2;
+
+/// State of a token account.
+#[repr(u8)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccountState {
+    /// Account is not initialized.
+    Uninitialized = 0,
+
+    /// Account is initialized.
+    Initialized = 1,
+
+    /// Account is frozen by the freeze authority of the mint.
+    Frozen = 2,
+}
+
+/// Zero-copy view over the data of a token account.
+///
+/// The view works with both SPL Token and Token-2022 accounts; the extensions of
+/// Token-2022 accounts are ignored.
+pub struct TokenAccount<'a> {
+    /// Data of the token account.
+    data: Ref<'a, [u8]>,
+}
+
+impl<'a> TokenAccount<'a> {
+    /// Creates a view over a token account.
+    ///
+    /// Returns `ProgramError::InvalidAccountOwner` if the account is not owned by a
+    /// token program, `ProgramError::InvalidAccountData` if the account data is not
+    /// a token account and `ProgramError::UninitializedAccount` if the token account
+    /// is not initialized.
+    pub fn try_from(account: &'a AccountInfo) -> Result<Self, ProgramError> {
+        check_owner(account)?;
+
+        let data = account.try_borrow_data()?;
+
+        if !has_type(&data, ACCOUNT_LEN, TOKEN_ACCOUNT_TYPE) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let account = Self { data };
+
+        if account.state() == AccountState::Uninitialized {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(account)
+    }
+
+    /// Returns the mint of the token account.
+    #[inline(always)]
+    pub fn mint(&self) -> &Pubkey {
+        pubkey_at(&self.data, 0)
+    }
+
+    /// Returns the owner of the token account.
+    #[inline(always)]
+    pub fn owner(&self) -> &Pubkey {
+        pubkey_at(&self.data, 32)
+    }
+
+    /// Returns the amount of tokens held by the token account.
+    #[inline(always)]
+    pub fn amount(&self) -> u64 {
+        u64_at(&self.data, 64)
+    }
+
+    /// Returns the delegate of the token account.
+    #[inline(always)]
+    pub fn delegate(&self) -> Option<&Pubkey> {
+        option_pubkey_at(&self.data, 72)
+    }
+
+    /// Returns the state of the token account.
+    #[inline(always)]
+    pub fn state(&self) -> AccountState {
+        match self.data[108] {
+            1 => AccountState::Initialized,
+            2 => AccountState::Frozen,
+            _ => AccountState::Uninitialized,
+        }
+    }
+
+    /// Indicates whether the token account is frozen.
+    #[inline(always)]
+    pub fn is_frozen(&self) -> bool {
+        self.state() == AccountState::Frozen
+    }
+
+    /// Indicates whether the token account is a native (wrapped SOL) account.
+    #[inline(always)]
+    pub fn is_native(&self) -> bool {
+        u32_at(&self.data, 109) != 0
+    }
+
+    /// Returns the rent-exempt reserve of a native (wrapped SOL) token account.
+    ///
+    /// The reserve is `None` when the token account is not a native account.
+    #[inline(always)]
+    pub fn native_reserve(&self) -> Option<u64> {
+        self.is_native().then(|| u64_at(&self.data, 113))
+    }
+
+    /// Returns the amount of tokens the delegate is approved for.
+    #[inline(always)]
+    pub fn delegated_amount(&self) -> u64 {
+        u64_at(&self.data, 121)
+    }
+
+    /// Returns the close authority of the token account.
+    #[inline(always)]
+    pub fn close_authority(&self) -> Option<&Pubkey> {
+        option_pubkey_at(&self.data, 129)
+    }
+}
+
+/// Zero-copy view over the data of a mint.
+///
+/// The view works with both SPL Token and Token-2022 mints; the extensions of
+/// Token-2022 mints are ignored.
+pub struct Mint<'a> {
+    /// Data of the mint.
+    data: Ref<'a, [u8]>,
+}
+
+impl<'a> Mint<'a> {
+    /// Creates a view over a mint.
+    ///
+    /// Returns `ProgramError::InvalidAccountOwner` if the account is not owned by a
+    /// token program, `ProgramError::InvalidAccountData` if the account data is not
+    /// a mint and `ProgramError::UninitializedAccount` if the mint is not
+    /// initialized.
+    pub fn try_from(account: &'a AccountInfo) -> Result<Self, ProgramError> {
+        check_owner(account)?;
+
+        let data = account.try_borrow_data()?;
+
+        if !has_type(&data, MINT_LEN, MINT_ACCOUNT_TYPE) {
+            return Err(ProgramError::InvalidAccountData);
+        }
+
+        let mint = Self { data };
+
+        if !mint.is_initialized() {
+            return Err(ProgramError::UninitializedAccount);
+        }
+
+        Ok(mint)
+    }
+
+    /// Returns the mint authority of the mint.
+    #[inline(always)]
+    pub fn mint_authority(&self) -> Option<&Pubkey> {
+        option_pubkey_at(&self.data, 0)
+    }
+
+    /// Returns the total supply of the mint.
+    #[inline(always)]
+    pub fn supply(&self) -> u64 {
+        u64_at(&self.data, 36)
+    }
+
+    /// Returns the number of decimals of the mint.
+    #[inline(always)]
+    pub fn decimals(&self) -> u8 {
+        self.data[44]
+    }
+
+    /// Indicates whether the mint is initialized.
+    #[inline(always)]
+    pub fn is_initialized(&self) -> bool {
+        self.data[45] != 0
+    }
+
+    /// Returns the freeze authority of the mint.
+    #[inline(always)]
+    pub fn freeze_authority(&self) -> Option<&Pubkey> {
+        option_pubkey_at(&self.data, 46)
+    }
+}
+
+/// Checks that the account is owned by a token program.
+#[inline(always)]
+fn check_owner(account: &AccountInfo) -> Result<(), ProgramError> {
+    let owner = account.owner();
+
+    if owner != &ID && owner != &token_2022::ID {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    Ok(())
+}
+
+/// Indicates whether the data is a token account or mint.
+///
+/// Accounts with extensions are longer than a token account and store their account
+/// type after the base token account (mints are padded to the same length).
+#[inline(always)]
+fn has_type(data: &[u8], base_len: usize, account_type: u8) -> bool {
+    data.len() == base_len
+        || (data.len() > ACCOUNT_LEN
+            && data.len() != MULTISIG_LEN
+            && data[ACCOUNT_TYPE_OFFSET] == account_type)
+}
+
+/// Returns the public key at the given offset.
+#[inline(always)]
+fn pubkey_at(data: &[u8], offset: usize) -> &Pubkey {
+    unsafe { &*(data[offset..offset + 32].as_ptr() as *const Pubkey) }
+}
+
+/// Returns the optional public key (`COption<Pubkey>`) at the given offset.
+#[inline(always)]
+fn option_pubkey_at(data: &[u8], offset: usize) -> Option<&Pubkey> {
+    (u32_at(data, offset) != 0).then(|| pubkey_at(data, offset + 4))
+}
+
+/// Reads a little-endian `u32` at the given offset.
+#[inline(always)]
+fn u32_at(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+/// Reads a little-endian `u64` at the given offset.
+#[inline(always)]
+fn u64_at(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
@@ -0,0 +1,584 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Token metadata interface CPI functions and state.
+//!
+//! Token-2022 implements the token metadata interface, storing the metadata as an
+//! extension of the mint account.
+
+use solana_program::{entrypoint::ProgramResult, program_error::ProgramError, pubkey::Pubkey};
+
+use super::{find_extension, ID};
+use crate::{
+    cpi::{self, invoke_signed_c, CAccountInfo, CAccountMeta, CInstruction, CSigner},
+    AccountInfo, Ref,
+};
+
+/// Maximum size of the instruction data of a metadata instruction.
+///
+/// The instruction data is written to a stack buffer, which is bounded by the size
+/// of a transaction packet.
+pub const MAX_DATA_LEN: usize = 1232;
+
+/// Type of the token metadata extension.
+const TOKEN_METADATA_EXTENSION: u16 = 19;
+
+/// Discriminator of the initialize instruction.
+const INITIALIZE_DISCRIMINATOR: [u8; 8] = [210, 225, 30, 162, 88, 184, 77, 141];
+
+/// Discriminator of the update field instruction.
+const UPDATE_FIELD_DISCRIMINATOR: [u8; 8] = [221, 233, 49, 45, 181, 202, 220, 200];
+
+/// Discriminator of the remove key instruction.
+const REMOVE_KEY_DISCRIMINATOR: [u8; 8] = [234, 18, 32, 56, 89, 141, 37, 181];
+
+/// Field of the token metadata.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Field<'a> {
+    /// Name of the token.
+    Name,
+
+    /// Symbol of the token.
+    Symbol,
+
+    /// URI of the token.
+    Uri,
+
+    /// Additional metadata key.
+    Key(&'a str),
+}
+
+/// Initialize the token metadata of a mint.
+///
+/// Returns `ProgramError::InvalidInstructionData` if the instruction data exceeds
+/// `MAX_DATA_LEN`.
+///
+/// # Arguments
+///
+/// * `metadata`: Metadata account (the mint when the metadata is stored in the mint).
+/// * `update_authority`: Update authority of the metadata.
+/// * `mint`: Mint account.
+/// * `mint_authority`: Mint authority account.
+/// * `name`: Name of the token.
+/// * `symbol`: Symbol of the token.
+/// * `uri`: URI of the token.
+pub fn initialize(
+    metadata: &AccountInfo,
+    update_authority: &AccountInfo,
+    mint: &AccountInfo,
+    mint_authority: &AccountInfo,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+) -> ProgramResult {
+    _initialize_signed(
+        metadata,
+        update_authority,
+        mint,
+        mint_authority,
+        name,
+        symbol,
+        uri,
+        &[],
+    )
+}
+
+/// Initialize the token metadata of a mint with a program signed instruction.
+///
+/// Returns `ProgramError::InvalidInstructionData` if the instruction data exceeds
+/// `MAX_DATA_LEN`.
+///
+/// # Arguments
+///
+/// * `metadata`: Metadata account (the mint when the metadata is stored in the mint).
+/// * `update_authority`: Update authority of the metadata.
+/// * `mint`: Mint account.
+/// * `mint_authority`: Mint authority account.
+/// * `name`: Name of the token.
+/// * `symbol`: Symbol of the token.
+/// * `uri`: URI of the token.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_signed<const SEEDS: usize>(
+    metadata: &AccountInfo,
+    update_authority: &AccountInfo,
+    mint: &AccountInfo,
+    mint_authority: &AccountInfo,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _initialize_signed(
+        metadata,
+        update_authority,
+        mint,
+        mint_authority,
+        name,
+        symbol,
+        uri,
+        &signer,
+    )
+}
+
+/// Update a field of the token metadata.
+///
+/// Updating a `Field::Key` adds the key to the additional metadata if it does not
+/// exist. The metadata account might need to be reallocated (e.g., funded) before
+/// the update when the new value is larger.
+///
+/// # Arguments
+///
+/// * `metadata`: Metadata account.
+/// * `update_authority`: Update authority of the metadata.
+/// * `field`: Field to update.
+/// * `value`: New value of the field.
+pub fn update_field(
+    metadata: &AccountInfo,
+    update_authority: &AccountInfo,
+    field: Field,
+    value: &str,
+) -> ProgramResult {
+    _update_field_signed(metadata, update_authority, field, value, &[])
+}
+
+/// Update a field of the token metadata with a program signed instruction.
+///
+/// # Arguments
+///
+/// * `metadata`: Metadata account.
+/// * `update_authority`: Update authority of the metadata.
+/// * `field`: Field to update.
+/// * `value`: New value of the field.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+pub fn update_field_signed<const SEEDS: usize>(
+    metadata: &AccountInfo,
+    update_authority: &AccountInfo,
+    field: Field,
+    value: &str,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _update_field_signed(metadata, update_authority, field, value, &signer)
+}
+
+/// Remove a key from the additional metadata.
+///
+/// # Arguments
+///
+/// * `metadata`: Metadata account.
+/// * `update_authority`: Update authority of the metadata.
+/// * `key`: Key to remove.
+/// * `idempotent`: Indicates whether the instruction succeeds when the key does not exist.
+pub fn remove_key(
+    metadata: &AccountInfo,
+    update_authority: &AccountInfo,
+    key: &str,
+    idempotent: bool,
+) -> ProgramResult {
+    _remove_key_signed(metadata, update_authority, key, idempotent, &[])
+}
+
+/// Remove a key from the additional metadata with a program signed instruction.
+///
+/// # Arguments
+///
+/// * `metadata`: Metadata account.
+/// * `update_authority`: Update authority of the metadata.
+/// * `key`: Key to remove.
+/// * `idempotent`: Indicates whether the instruction succeeds when the key does not exist.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+pub fn remove_key_signed<const SEEDS: usize>(
+    metadata: &AccountInfo,
+    update_authority: &AccountInfo,
+    key: &str,
+    idempotent: bool,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _remove_key_signed(metadata, update_authority, key, idempotent, &signer)
+}
+
+/// Zero-copy view over the token metadata stored in a mint account.
+pub struct TokenMetadata<'a> {
+    /// Data of the mint account.
+    data: Ref<'a, [u8]>,
+
+    /// Offset of the metadata.
+    offset: usize,
+
+    /// Ranges of the name, symbol and URI.
+    fields: [(usize, usize); 3],
+
+    /// Offset of the additional metadata.
+    additional_metadata: usize,
+}
+
+impl<'a> TokenMetadata<'a> {
+    /// Creates a view over the token metadata of a mint.
+    ///
+    /// Returns `ProgramError::InvalidAccountOwner` if the mint is not owned by the
+    /// Token-2022 program and `ProgramError::InvalidAccountData` if the mint does not
+    /// have a valid token metadata extension.
+    pub fn try_from(mint: &'a AccountInfo) -> Result<Self, ProgramError> {
+        if mint.owner() != &ID {
+            return Err(ProgramError::InvalidAccountOwner);
+        }
+
+        let data = mint.try_borrow_data()?;
+
+        let value = find_extension(&data, TOKEN_METADATA_EXTENSION)?
+            .ok_or(ProgramError::InvalidAccountData)?;
+        let offset = value.as_ptr() as usize - data.as_ptr() as usize;
+
+        // -    0..32: update authority (zeroed when not set)
+        // -   32..64: mint
+        // -   64..  : name, symbol and uri
+        // -         : additional metadata
+        let mut cursor = 2 * std::mem::size_of::<Pubkey>();
+        let mut fields = [(0, 0); 3];
+
+        for field in fields.iter_mut() {
+            let (start, end) = read_str(value, cursor)?;
+            *field = (offset + start, offset + end);
+            cursor = end;
+        }
+
+        let additional_metadata = cursor;
+        let count = read_u32(value, cursor)?;
+        cursor += std::mem::size_of::<u32>();
+
+        for _ in 0..count {
+            // key and value
+            cursor = read_str(value, cursor)?.1;
+            cursor = read_str(value, cursor)?.1;
+        }
+
+        Ok(Self {
+            offset,
+            fields,
+            additional_metadata: offset + additional_metadata,
+            data,
+        })
+    }
+
+    /// Returns the update authority of the metadata.
+    #[inline(always)]
+    pub fn update_authority(&self) -> Option<&Pubkey> {
+        let authority = self.pubkey_at(self.offset);
+        (authority != &Pubkey::default()).then_some(authority)
+    }
+
+    /// Returns the mint of the metadata.
+    #[inline(always)]
+    pub fn mint(&self) -> &Pubkey {
+        self.pubkey_at(self.offset + std::mem::size_of::<Pubkey>())
+    }
+
+    /// Returns the name of the token.
+    #[inline(always)]
+    pub fn name(&self) -> &str {
+        self.str_at(self.fields[0])
+    }
+
+    /// Returns the symbol of the token.
+    #[inline(always)]
+    pub fn symbol(&self) -> &str {
+        self.str_at(self.fields[1])
+    }
+
+    /// Returns the URI of the token.
+    #[inline(always)]
+    pub fn uri(&self) -> &str {
+        self.str_at(self.fields[2])
+    }
+
+    /// Returns an iterator over the additional metadata key-value pairs.
+    #[inline(always)]
+    pub fn additional_metadata(&self) -> AdditionalMetadata<'_> {
+        AdditionalMetadata {
+            data: &self.data[self.additional_metadata + std::mem::size_of::<u32>()..],
+            remaining: read_u32(&self.data, self.additional_metadata).unwrap(),
+        }
+    }
+
+    /// Returns the value of an additional metadata key.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.additional_metadata()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Returns the public key at the given offset.
+    #[inline(always)]
+    fn pubkey_at(&self, offset: usize) -> &Pubkey {
+        unsafe { &*(self.data[offset..offset + 32].as_ptr() as *const Pubkey) }
+    }
+
+    /// Returns the (validated) string in the given range.
+    #[inline(always)]
+    fn str_at(&self, (start, end): (usize, usize)) -> &str {
+        unsafe { std::str::from_utf8_unchecked(&self.data[start..end]) }
+    }
+}
+
+/// Iterator over the additional metadata of a token metadata.
+pub struct AdditionalMetadata<'a> {
+    /// Remaining (validated) key-value pairs data.
+    data: &'a [u8],
+
+    /// Number of remaining key-value pairs.
+    remaining: u32,
+}
+
+impl<'a> Iterator for AdditionalMetadata<'a> {
+    type Item = (&'a str, &'a str);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        self.remaining -= 1;
+
+        let (start, end) = read_str(self.data, 0).ok()?;
+        let key = unsafe { std::str::from_utf8_unchecked(&self.data[start..end]) };
+
+        let (start, value_end) = read_str(self.data, end).ok()?;
+        let value = unsafe { std::str::from_utf8_unchecked(&self.data[start..value_end]) };
+
+        self.data = &self.data[value_end..];
+
+        Some((key, value))
+    }
+}
+
+//-- Internal functions
+
+/// Instruction data written to a stack buffer.
+struct InstructionData {
+    /// Buffer of the instruction data.
+    data: [std::mem::MaybeUninit<u8>; MAX_DATA_LEN],
+
+    /// Number of bytes written.
+    len: usize,
+}
+
+impl InstructionData {
+    /// Creates the instruction data with the given discriminator.
+    #[inline(always)]
+    fn new(discriminator: &[u8]) -> Self {
+        let mut data = Self {
+            data: unsafe { std::mem::MaybeUninit::uninit().assume_init() },
+            len: 0,
+        };
+        // the discriminator always fits in the buffer
+        data.write(discriminator).unwrap();
+        data
+    }
+
+    /// Writes the bytes to the instruction data.
+    #[inline(always)]
+    fn write(&mut self, bytes: &[u8]) -> Result<(), ProgramError> {
+        if self.len + bytes.len() > MAX_DATA_LEN {
+            return Err(ProgramError::InvalidInstructionData);
+        }
+
+        unsafe {
+            std::ptr::copy_nonoverlapping(
+                bytes.as_ptr(),
+                self.data.as_mut_ptr().add(self.len) as *mut u8,
+                bytes.len(),
+            );
+        }
+        self.len += bytes.len();
+
+        Ok(())
+    }
+
+    /// Writes a length-prefixed string to the instruction data.
+    #[inline(always)]
+    fn write_str(&mut self, value: &str) -> Result<(), ProgramError> {
+        self.write(&(value.len() as u32).to_le_bytes())?;
+        self.write(value.as_bytes())
+    }
+
+    /// Returns the written instruction data.
+    #[inline(always)]
+    fn as_slice(&self) -> &[u8] {
+        unsafe { std::slice::from_raw_parts(self.data.as_ptr() as *const u8, self.len) }
+    }
+}
+
+/// Invokes a token metadata instruction.
+#[inline(always)]
+fn invoke_metadata<const ACCOUNTS: usize>(
+    instruction_accounts: [CAccountMeta; ACCOUNTS],
+    accounts: [&AccountInfo; ACCOUNTS],
+    instruction_data: &InstructionData,
+    signer: &[CSigner],
+) -> ProgramResult {
+    let instruction_data = instruction_data.as_slice();
+
+    let instruction = CInstruction {
+        program_id: &ID,
+        accounts: instruction_accounts.as_ptr(),
+        accounts_len: instruction_accounts.len() as u64,
+        data: instruction_data.as_ptr(),
+        data_len: instruction_data.len() as u64,
+    };
+
+    let account_infos: [CAccountInfo; ACCOUNTS] = accounts.map(CAccountInfo::from);
+
+    invoke_signed_c(&instruction, &account_infos, signer)
+}
+
+/// Initialize the token metadata of a mint either with or without a program
+/// signed instruction.
+#[allow(clippy::too_many_arguments)]
+fn _initialize_signed(
+    metadata: &AccountInfo,
+    update_authority: &AccountInfo,
+    mint: &AccountInfo,
+    mint_authority: &AccountInfo,
+    name: &str,
+    symbol: &str,
+    uri: &str,
+    signer: &[CSigner],
+) -> ProgramResult {
+    let mut instruction_accounts: [CAccountMeta; 4] = [
+        metadata.into(),
+        update_authority.into(),
+        mint.into(),
+        mint_authority.into(),
+    ];
+    // mint authority must sign the instruction
+    instruction_accounts[3].is_signer = true;
+
+    // -   0..8: instruction discriminator
+    // -   8.. : name, symbol and uri
+    let mut instruction_data = InstructionData::new(&INITIALIZE_DISCRIMINATOR);
+    instruction_data.write_str(name)?;
+    instruction_data.write_str(symbol)?;
+    instruction_data.write_str(uri)?;
+
+    invoke_metadata(
+        instruction_accounts,
+        [metadata, update_authority, mint, mint_authority],
+        &instruction_data,
+        signer,
+    )
+}
+
+/// Update a field of the token metadata either with or without a program signed
+/// instruction.
+fn _update_field_signed(
+    metadata: &AccountInfo,
+    update_authority: &AccountInfo,
+    field: Field,
+    value: &str,
+    signer: &[CSigner],
+) -> ProgramResult {
+    let mut instruction_accounts: [CAccountMeta; 2] = [metadata.into(), update_authority.into()];
+    // update authority must sign the instruction
+    instruction_accounts[1].is_signer = true;
+
+    // -   0..8: instruction discriminator
+    // -      8: field
+    // -   9.. : key (for additional metadata) and value
+    let mut instruction_data = InstructionData::new(&UPDATE_FIELD_DISCRIMINATOR);
+
+    match field {
+        Field::Name => instruction_data.write(&[0])?,
+        Field::Symbol => instruction_data.write(&[1])?,
+        Field::Uri => instruction_data.write(&[2])?,
+        Field::Key(key) => {
+            instruction_data.write(&[3])?;
+            instruction_data.write_str(key)?;
+        }
+    }
+
+    instruction_data.write_str(value)?;
+
+    invoke_metadata(
+        instruction_accounts,
+        [metadata, update_authority],
+        &instruction_data,
+        signer,
+    )
+}
+
+/// Remove a key from the additional metadata either with or without a program
+/// signed instruction.
+fn _remove_key_signed(
+    metadata: &AccountInfo,
+    update_authority: &AccountInfo,
+    key: &str,
+    idempotent: bool,
+    signer: &[CSigner],
+) -> ProgramResult {
+    let mut instruction_accounts: [CAccountMeta; 2] = [metadata.into(), update_authority.into()];
+    // update authority must sign the instruction
+    instruction_accounts[1].is_signer = true;
+
+    // -   0..8: instruction discriminator
+    // -      8: idempotent flag
+    // -   9.. : key
+    let mut instruction_data = InstructionData::new(&REMOVE_KEY_DISCRIMINATOR);
+    instruction_data.write(&[idempotent as u8])?;
+    instruction_data.write_str(key)?;
+
+    invoke_metadata(
+        instruction_accounts,
+        [metadata, update_authority],
+        &instruction_data,
+        signer,
+    )
+}
+
+/// Reads a little-endian `u32` at the given offset.
+#[inline(always)]
+fn read_u32(data: &[u8], offset: usize) -> Result<u32, ProgramError> {
+    data.get(offset..offset + std::mem::size_of::<u32>())
+        .map(|value| u32::from_le_bytes(value.try_into().unwrap()))
+        .ok_or(ProgramError::InvalidAccountData)
+}
+
+/// Reads a length-prefixed UTF-8 string at the given offset, returning its range.
+#[inline(always)]
+fn read_str(data: &[u8], offset: usize) -> Result<(usize, usize), ProgramError> {
+    let len = read_u32(data, offset)? as usize;
+    let start = offset + std::mem::size_of::<u32>();
+
+    data.get(start..start + len)
+        .and_then(|value| std::str::from_utf8(value).ok())
+        .map(|_| (start, start + len))
+        .ok_or(ProgramError::InvalidAccountData)
+}
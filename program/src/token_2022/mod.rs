@@ -0,0 +1,555 @@
+// Copyright (c) 2024 nifty-oss maintainers
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Token-2022 Program CPI functions.
+
+pub mod metadata;
+
+use solana_program::{
+    clock::{Clock, Epoch},
+    entrypoint::ProgramResult,
+    program_error::ProgramError,
+    pubkey,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    cpi::{self, invoke_signed_c, CAccountInfo, CAccountMeta, CInstruction, CSigner},
+    token::{invoke_with_authority, Authority},
+    AccountInfo,
+};
+
+/// Token-2022 program id.
+pub const ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+/// Size of a token account without extensions.
+pub const BASE_ACCOUNT_LEN: usize = 165;
+
+/// Size of a mint without extensions.
+pub const BASE_MINT_LEN: usize = 82;
+
+/// Size of a multisig account.
+///
+/// Accounts with extensions are padded to never have the same size as a multisig.
+const MULTISIG_LEN: usize = 355;
+
+/// Size of the type and length of an extension.
+const EXTENSION_HEADER_LEN: usize = 4;
+
+/// Number of basis points in one (100%).
+const ONE_IN_BASIS_POINTS: u128 = 10_000;
+
+/// Mint and token account extensions.
+///
+/// Only extensions with a fixed size are listed; variable-length extensions (e.g.,
+/// token metadata) are allocated by the Token-2022 program when the extension is
+/// initialized, which only requires the account to be funded.
+#[repr(u16)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExtensionType {
+    /// Transfer fee configuration of a mint.
+    TransferFeeConfig = 1,
+
+    /// Withheld transfer fees.
+    TransferFeeAmount = 2,
+
+    /// Authority to close a mint.
+    MintCloseAuthority = 3,
+
+    /// Confidential transfer configuration of a mint.
+    ConfidentialTransferMint = 4,
+
+    /// Confidential transfer state.
+    ConfidentialTransferAccount = 5,
+
+    /// Default state of new token accounts of a mint.
+    DefaultAccountState = 6,
+
+    /// Owner cannot be changed.
+    ImmutableOwner = 7,
+
+    /// Incoming transfers require a memo.
+    MemoTransfer = 8,
+
+    /// Tokens of a mint cannot be transferred.
+    NonTransferable = 9,
+
+    /// Interest configuration of a mint.
+    InterestBearingConfig = 10,
+
+    /// Restricts the use of the account in CPIs.
+    CpiGuard = 11,
+
+    /// Permanent delegate of a mint.
+    PermanentDelegate = 12,
+
+    /// Account of a non-transferable mint.
+    NonTransferableAccount = 13,
+
+    /// Transfer hook program of a mint.
+    TransferHook = 14,
+
+    /// Transfer hook state.
+    TransferHookAccount = 15,
+
+    /// Confidential transfer fee configuration of a mint.
+    ConfidentialTransferFeeConfig = 16,
+
+    /// Withheld confidential transfer fees.
+    ConfidentialTransferFeeAmount = 17,
+
+    /// Address of the metadata of a mint.
+    MetadataPointer = 18,
+
+    /// Address of the group configuration of a mint.
+    GroupPointer = 20,
+
+    /// Address of the group member configuration of a mint.
+    GroupMemberPointer = 22,
+}
+
+impl ExtensionType {
+    /// Returns the size of the extension data.
+    pub const fn data_len(&self) -> usize {
+        match self {
+            ExtensionType::TransferFeeConfig => 108,
+            ExtensionType::TransferFeeAmount => 8,
+            ExtensionType::MintCloseAuthority => 32,
+            ExtensionType::ConfidentialTransferMint => 65,
+            ExtensionType::ConfidentialTransferAccount => 295,
+            ExtensionType::DefaultAccountState => 1,
+            ExtensionType::ImmutableOwner => 0,
+            ExtensionType::MemoTransfer => 1,
+            ExtensionType::NonTransferable => 0,
+            ExtensionType::InterestBearingConfig => 52,
+            ExtensionType::CpiGuard => 1,
+            ExtensionType::PermanentDelegate => 32,
+            ExtensionType::NonTransferableAccount => 0,
+            ExtensionType::TransferHook => 64,
+            ExtensionType::TransferHookAccount => 1,
+            ExtensionType::ConfidentialTransferFeeConfig => 129,
+            ExtensionType::ConfidentialTransferFeeAmount => 64,
+            ExtensionType::MetadataPointer => 64,
+            ExtensionType::GroupPointer => 64,
+            ExtensionType::GroupMemberPointer => 64,
+        }
+    }
+}
+
+/// Returns the size of a token account with the given extensions.
+///
+/// Duplicated extensions are only accounted once.
+///
+/// # Arguments
+///
+/// * `extensions`: Extensions of the account.
+#[inline(always)]
+pub fn get_account_len(extensions: &[ExtensionType]) -> usize {
+    get_len(BASE_ACCOUNT_LEN, extensions)
+}
+
+/// Returns the size of a mint with the given extensions.
+///
+/// Duplicated extensions are only accounted once.
+///
+/// # Arguments
+///
+/// * `extensions`: Extensions of the mint.
+#[inline(always)]
+pub fn get_mint_len(extensions: &[ExtensionType]) -> usize {
+    get_len(BASE_MINT_LEN, extensions)
+}
+
+/// Returns the data of an extension of a mint or token account.
+///
+/// Returns `ProgramError::InvalidAccountData` if the extensions are malformed.
+///
+/// # Arguments
+///
+/// * `data`: Data of the mint or token account.
+/// * `extension`: Type of the extension.
+pub(crate) fn find_extension(data: &[u8], extension: u16) -> Result<Option<&[u8]>, ProgramError> {
+    // the extensions follow the base account (mints are padded to the same
+    // length) and the account type
+    let mut offset = BASE_ACCOUNT_LEN + 1;
+
+    while offset + EXTENSION_HEADER_LEN <= data.len() {
+        // -   0..2: extension type
+        // -   2..4: extension length
+        let extension_type = u16::from_le_bytes([data[offset], data[offset + 1]]);
+        let len = u16::from_le_bytes([data[offset + 2], data[offset + 3]]) as usize;
+
+        // uninitialized extension marks the end of the extensions
+        if extension_type == 0 {
+            break;
+        }
+
+        offset += EXTENSION_HEADER_LEN;
+
+        let value = data
+            .get(offset..offset + len)
+            .ok_or(ProgramError::InvalidAccountData)?;
+
+        if extension_type == extension {
+            return Ok(Some(value));
+        }
+
+        offset += len;
+    }
+
+    Ok(None)
+}
+
+/// Initialize the immutable owner extension of a token account.
+///
+/// This instruction must be invoked before the account is initialized.
+///
+/// # Arguments
+///
+/// * `account`: Token account to initialize.
+pub fn initialize_immutable_owner(account: &AccountInfo) -> ProgramResult {
+    let instruction_accounts: [CAccountMeta; 1] = [account.into()];
+
+    // initialize immutable owner instruction has a '22' discriminator
+    let instruction_data = [22];
+
+    let instruction = CInstruction {
+        program_id: &ID,
+        accounts: instruction_accounts.as_ptr(),
+        accounts_len: instruction_accounts.len() as u64,
+        data: instruction_data.as_ptr(),
+        data_len: instruction_data.len() as u64,
+    };
+
+    let account_infos: [CAccountInfo; 1] = [account.into()];
+
+    invoke_signed_c(&instruction, &account_infos, &[])
+}
+
+/// Withdraw the lamports in excess of the rent-exempt minimum of a mint or token
+/// account.
+///
+/// # Arguments
+///
+/// * `source`: Mint or token account to withdraw from.
+/// * `destination`: Recipient account.
+/// * `authority`: Mint authority or owner of the token account.
+pub fn withdraw_excess_lamports(
+    source: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+) -> ProgramResult {
+    _withdraw_excess_lamports_signed(source, destination, authority, &[])
+}
+
+/// Withdraw the lamports in excess of the rent-exempt minimum of a mint or token
+/// account with a program signed instruction.
+///
+/// # Arguments
+///
+/// * `source`: Mint or token account to withdraw from.
+/// * `destination`: Recipient account.
+/// * `authority`: Mint authority or owner of the token account.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+pub fn withdraw_excess_lamports_signed<const SEEDS: usize>(
+    source: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _withdraw_excess_lamports_signed(source, destination, authority, &signer)
+}
+
+/// Initialize the metadata pointer extension of a mint.
+///
+/// This instruction must be invoked before the mint is initialized.
+///
+/// # Arguments
+///
+/// * `mint`: Mint account.
+/// * `authority`: Authority that can update the metadata address.
+/// * `metadata_address`: Address of the account holding the metadata.
+#[inline(always)]
+pub fn initialize_metadata_pointer(
+    mint: &AccountInfo,
+    authority: Option<&Pubkey>,
+    metadata_address: Option<&Pubkey>,
+) -> ProgramResult {
+    // metadata pointer extension instruction has a '39' discriminator
+    initialize_pointer(mint, 39, authority, metadata_address)
+}
+
+/// Initialize the group pointer extension of a mint.
+///
+/// This instruction must be invoked before the mint is initialized.
+///
+/// # Arguments
+///
+/// * `mint`: Mint account.
+/// * `authority`: Authority that can update the group address.
+/// * `group_address`: Address of the account holding the group configuration.
+#[inline(always)]
+pub fn initialize_group_pointer(
+    mint: &AccountInfo,
+    authority: Option<&Pubkey>,
+    group_address: Option<&Pubkey>,
+) -> ProgramResult {
+    // group pointer extension instruction has a '40' discriminator
+    initialize_pointer(mint, 40, authority, group_address)
+}
+
+/// Returns the transfer fee of a mint for an amount at the given epoch.
+///
+/// The fee is zero when the mint does not have a transfer fee configuration.
+///
+/// Returns `ProgramError::InvalidAccountOwner` if the mint is not owned by the
+/// Token-2022 program and `ProgramError::InvalidAccountData` if the extensions of
+/// the mint are malformed.
+///
+/// # Arguments
+///
+/// * `mint`: Mint account.
+/// * `amount`: Amount of tokens being transferred.
+/// * `epoch`: Epoch of the transfer.
+pub fn calculate_transfer_fee(
+    mint: &AccountInfo,
+    amount: u64,
+    epoch: Epoch,
+) -> Result<u64, ProgramError> {
+    if mint.owner() != &ID {
+        return Err(ProgramError::InvalidAccountOwner);
+    }
+
+    let data = mint.try_borrow_data()?;
+
+    let config = match find_extension(&data, ExtensionType::TransferFeeConfig as u16)? {
+        Some(config) if config.len() == ExtensionType::TransferFeeConfig.data_len() => config,
+        Some(_) => return Err(ProgramError::InvalidAccountData),
+        None => return Ok(0),
+    };
+
+    // -    0..32: transfer fee config authority
+    // -   32..64: withdraw withheld authority
+    // -   64..72: withheld amount
+    // -   72..90: older transfer fee
+    // -  90..108: newer transfer fee
+    //
+    // each transfer fee is:
+    // -     0..8: epoch
+    // -    8..16: maximum fee
+    // -   16..18: transfer fee basis points
+    let newer_epoch = u64::from_le_bytes(config[90..98].try_into().unwrap());
+    let fee = if epoch >= newer_epoch {
+        &config[90..108]
+    } else {
+        &config[72..90]
+    };
+
+    let maximum_fee = u64::from_le_bytes(fee[8..16].try_into().unwrap());
+    let basis_points = u16::from_le_bytes(fee[16..18].try_into().unwrap()) as u128;
+
+    if basis_points == 0 || amount == 0 {
+        return Ok(0);
+    }
+
+    // the fee is rounded up
+    let fee = (amount as u128 * basis_points).div_ceil(ONE_IN_BASIS_POINTS);
+
+    Ok(std::cmp::min(fee, maximum_fee as u128) as u64)
+}
+
+/// Transfer tokens from one token account to another, checking the mint, decimals
+/// and transfer fee.
+///
+/// The expected fee is calculated from the transfer fee configuration of the mint
+/// at the current epoch.
+///
+/// # Arguments
+///
+/// * `source`: Token account to transfer from.
+/// * `mint`: Mint of the token accounts.
+/// * `destination`: Token account to transfer to.
+/// * `authority`: Owner or delegate of the source account.
+/// * `amount`: Amount of tokens to transfer.
+/// * `decimals`: Expected number of decimals of the mint.
+#[inline(always)]
+pub fn transfer_checked_with_fee(
+    source: &AccountInfo,
+    mint: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    decimals: u8,
+) -> ProgramResult {
+    _transfer_checked_with_fee_signed(source, mint, destination, authority, amount, decimals, &[])
+}
+
+/// Transfer tokens from one token account to another, checking the mint, decimals
+/// and transfer fee, with a program signed instruction.
+///
+/// The expected fee is calculated from the transfer fee configuration of the mint
+/// at the current epoch.
+///
+/// # Arguments
+///
+/// * `source`: Token account to transfer from.
+/// * `mint`: Mint of the token accounts.
+/// * `destination`: Token account to transfer to.
+/// * `authority`: Owner or delegate of the source account.
+/// * `amount`: Amount of tokens to transfer.
+/// * `decimals`: Expected number of decimals of the mint.
+/// * `signer_seeds`: Seeds used to sign the instruction.
+#[inline(always)]
+pub fn transfer_checked_with_fee_signed<const SEEDS: usize>(
+    source: &AccountInfo,
+    mint: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    decimals: u8,
+    signer_seeds: &[&[u8]; SEEDS],
+) -> ProgramResult {
+    let seeds = cpi::signer_seeds(signer_seeds);
+    let signer = [CSigner {
+        seeds: seeds.as_ptr(),
+        len: SEEDS as u64,
+    }];
+
+    _transfer_checked_with_fee_signed(
+        source,
+        mint,
+        destination,
+        authority,
+        amount,
+        decimals,
+        &signer,
+    )
+}
+
+//-- Internal functions
+
+/// Returns the size of an account with the given extensions.
+fn get_len(base_len: usize, extensions: &[ExtensionType]) -> usize {
+    if extensions.is_empty() {
+        return base_len;
+    }
+
+    let extensions_len = extensions
+        .iter()
+        .enumerate()
+        .filter(|(i, extension)| !extensions[..*i].contains(extension))
+        .map(|(_, extension)| EXTENSION_HEADER_LEN + extension.data_len())
+        .sum::<usize>();
+
+    // the account type follows the base account (mints are padded to the same
+    // length)
+    let len = BASE_ACCOUNT_LEN + 1 + extensions_len;
+
+    if len == MULTISIG_LEN {
+        len + std::mem::size_of::<ExtensionType>()
+    } else {
+        len
+    }
+}
+
+/// Initialize a pointer extension (metadata or group) of a mint.
+fn initialize_pointer(
+    mint: &AccountInfo,
+    discriminator: u8,
+    authority: Option<&Pubkey>,
+    address: Option<&Pubkey>,
+) -> ProgramResult {
+    let instruction_accounts: [CAccountMeta; 1] = [mint.into()];
+
+    // -      0: extension instruction discriminator
+    // -      1: initialize instruction discriminator
+    // -  2..34: authority (zeroed when not set)
+    // - 34..66: address (zeroed when not set)
+    let mut instruction_data = [0; 66];
+    instruction_data[0] = discriminator;
+    // initialize instruction has a '0' discriminator
+    if let Some(authority) = authority {
+        instruction_data[2..34].copy_from_slice(authority.as_ref());
+    }
+    if let Some(address) = address {
+        instruction_data[34..66].copy_from_slice(address.as_ref());
+    }
+
+    let instruction = CInstruction {
+        program_id: &ID,
+        accounts: instruction_accounts.as_ptr(),
+        accounts_len: instruction_accounts.len() as u64,
+        data: instruction_data.as_ptr(),
+        data_len: instruction_data.len() as u64,
+    };
+
+    let account_infos: [CAccountInfo; 1] = [mint.into()];
+
+    invoke_signed_c(&instruction, &account_infos, &[])
+}
+
+/// Withdraw the lamports in excess of the rent-exempt minimum of a mint or token
+/// account either with or without a program signed instruction.
+fn _withdraw_excess_lamports_signed(
+    source: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+    signer: &[CSigner],
+) -> ProgramResult {
+    // withdraw excess lamports instruction has a '38' discriminator
+    invoke_with_authority(&ID, &[source, destination], &authority, &[38], signer)
+}
+
+/// Transfer tokens from one token account to another, checking the mint, decimals
+/// and transfer fee, either with or without a program signed instruction.
+fn _transfer_checked_with_fee_signed(
+    source: &AccountInfo,
+    mint: &AccountInfo,
+    destination: &AccountInfo,
+    authority: Authority,
+    amount: u64,
+    decimals: u8,
+    signer: &[CSigner],
+) -> ProgramResult {
+    let fee = calculate_transfer_fee(mint, amount, Clock::get()?.epoch)?;
+
+    // -      0: extension instruction discriminator
+    // -      1: transfer checked with fee instruction discriminator
+    // -  2..10: amount
+    // -     10: decimals
+    // - 11..19: fee
+    let mut instruction_data = [0; 19];
+    // transfer fee extension instruction has a '26' discriminator
+    instruction_data[0] = 26;
+    // transfer checked with fee instruction has a '1' discriminator
+    instruction_data[1] = 1;
+    instruction_data[2..10].copy_from_slice(&amount.to_le_bytes());
+    instruction_data[10] = decimals;
+    instruction_data[11..19].copy_from_slice(&fee.to_le_bytes());
+
+    invoke_with_authority(
+        &ID,
+        &[source, mint, destination],
+        &authority,
+        &instruction_data,
+        signer,
+    )
+}